@@ -0,0 +1,391 @@
+use crate::parser::{nanos_to_std, parse_nanos, parse_span_value};
+use crate::unit::TimeUnit;
+use crate::{DurationError, SignedDuration, Span, ONE_MONTH_NANOSECOND, ONE_YEAR_NANOSECOND};
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+const ALL_UNITS: u16 = (1 << 10) - 1;
+
+/// A duration parser configured at runtime.
+///
+/// The `lowercase`, `no_calc` and `cn_unit` Cargo features only choose the defaults of
+/// [`DurationParser::new`]; every option can be changed per parser, so one binary can parse
+/// strict config files and lenient command line input differently. The free [`parse`](crate::parse)
+/// functions use the default parser.
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::{DurationParser, TimeUnit};
+/// use std::time::Duration;
+///
+/// let parser = DurationParser::new()
+///     .case_sensitive(true)
+///     .calc(false)
+///     .default_unit(TimeUnit::MilliSecond)
+///     .allowed_units([TimeUnit::Second, TimeUnit::MilliSecond]);
+///
+/// assert_eq!(parser.parse("1s 500"), Ok(Duration::from_millis(1500)));
+/// assert!(parser.parse("1S").is_err());
+/// assert!(parser.parse("1m").is_err());
+/// assert!(parser.parse("1s * 2").is_err());
+///
+/// let parser = DurationParser::new().cn_unit(true);
+/// assert_eq!(parser.parse("1时30分"), Ok(Duration::new(5400, 0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DurationParser {
+    pub(crate) case_sensitive: bool,
+    pub(crate) calc: bool,
+    pub(crate) cn_unit: bool,
+    pub(crate) default_unit: TimeUnit,
+    pub(crate) strict: bool,
+    pub(crate) numeric_negatives: bool,
+    pub(crate) numeric_fractions: bool,
+    /// Bit set indexed by `TimeUnit as u16`.
+    allowed_units: u16,
+    month_nanos: u128,
+    year_nanos: u128,
+}
+
+impl Default for DurationParser {
+    fn default() -> Self {
+        DurationParser::new()
+    }
+}
+
+impl DurationParser {
+    /// A parser with the defaults chosen by the enabled Cargo features: case insensitive unless
+    /// `lowercase` is enabled, with arithmetic unless `no_calc` is enabled and with Chinese units
+    /// if `cn_unit` is enabled. Bare numbers are seconds, a month is 30 days and a year 365 days.
+    pub const fn new() -> Self {
+        DurationParser {
+            case_sensitive: cfg!(feature = "lowercase"),
+            calc: !cfg!(feature = "no_calc"),
+            cn_unit: cfg!(feature = "cn_unit"),
+            default_unit: TimeUnit::Second,
+            strict: false,
+            numeric_negatives: true,
+            numeric_fractions: true,
+            allowed_units: ALL_UNITS,
+            month_nanos: ONE_MONTH_NANOSECOND as u128,
+            year_nanos: ONE_YEAR_NANOSECOND as u128,
+        }
+    }
+
+    /// Only accept unit names written in lowercase, e.g. reject `1H`, and ISO 8601 designators
+    /// written in uppercase, e.g. reject `pt1h`.
+    pub const fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Evaluate `+`, `-`, `*`, `/` and parentheses. When disabled, terms can only be
+    /// written next to each other and are added, e.g. `1h 30m`.
+    pub const fn calc(mut self, calc: bool) -> Self {
+        self.calc = calc;
+        self
+    }
+
+    /// Accept Chinese unit names such as `年`, `天` or `毫秒`.
+    pub const fn cn_unit(mut self, cn_unit: bool) -> Self {
+        self.cn_unit = cn_unit;
+        self
+    }
+
+    /// The unit of numbers written without one, e.g. `500`.
+    ///
+    /// `*` and `/` treat their operands as counts of this unit, so with milliseconds
+    /// `1s * 2` is two seconds.
+    pub const fn default_unit(mut self, unit: TimeUnit) -> Self {
+        self.default_unit = unit;
+        self
+    }
+
+    /// Strict mode: reject numbers written without a unit, such as `500`, instead of reading
+    /// them in the default unit. The right operand of `*` and `/` may still be a bare number.
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Accept negative numeric values such as `-30` when deserializing, e.g. from JSON or TOML.
+    /// Types that cannot be negative, such as `std::time::Duration`, reject them either way.
+    ///
+    /// This only applies to numbers read by the serde deserializers, e.g. with
+    /// [`DeserializeDuration::deserialize_duration_with`]; [`parse`](Self::parse) and the other
+    /// string parsers ignore it.
+    ///
+    /// [`DeserializeDuration::deserialize_duration_with`]: crate::DeserializeDuration::deserialize_duration_with
+    #[cfg(feature = "serde")]
+    pub const fn numeric_negatives(mut self, allow: bool) -> Self {
+        self.numeric_negatives = allow;
+        self
+    }
+
+    /// Accept fractional numeric values such as `1.5` when deserializing, e.g. from JSON or
+    /// TOML. Whole floats such as `30.0` are always accepted.
+    ///
+    /// Like [`numeric_negatives`](Self::numeric_negatives), this only applies to numbers read by
+    /// the serde deserializers; a string such as `"1.5"` is parsed as usual.
+    #[cfg(feature = "serde")]
+    pub const fn numeric_fractions(mut self, allow: bool) -> Self {
+        self.numeric_fractions = allow;
+        self
+    }
+
+    /// Reject every unit not listed here, in ISO 8601 input too. The default unit of bare
+    /// numbers is always allowed.
+    pub fn allowed_units(mut self, units: impl IntoIterator<Item = TimeUnit>) -> Self {
+        self.allowed_units = units
+            .into_iter()
+            .fold(0, |units, unit| units | 1 << unit as u16);
+        self
+    }
+
+    /// The length of the `mon` unit and of ISO 8601 months, 30 days by default.
+    pub const fn month_length(mut self, month: Duration) -> Self {
+        self.month_nanos = month.as_nanos();
+        self
+    }
+
+    /// The length of the `y` unit and of ISO 8601 years, 365 days by default.
+    pub const fn year_length(mut self, year: Duration) -> Self {
+        self.year_nanos = year.as_nanos();
+        self
+    }
+
+    /// Whether `unit` may be written out in the input.
+    pub(crate) fn allows(&self, unit: TimeUnit) -> bool {
+        self.allowed_units & 1 << unit as u16 != 0
+    }
+
+    /// Length of one `unit` in nanoseconds.
+    pub(crate) fn unit_nanos(&self, unit: TimeUnit) -> Decimal {
+        match unit {
+            TimeUnit::Year => Decimal::from_i128_with_scale(self.year_nanos as i128, 0),
+            TimeUnit::Month => Decimal::from_i128_with_scale(self.month_nanos as i128, 0),
+            unit => unit.nanos_per_unit().into(),
+        }
+    }
+
+    /// Parse `input` to `std::time::Duration`, see [`parse`](crate::parse).
+    pub fn parse(&self, input: impl AsRef<str>) -> Result<Duration, DurationError> {
+        let input = input.as_ref();
+        let nanos = parse_nanos(input, self)?;
+        nanos_to_std(nanos).map_err(|err| DurationError::from_error(input, err))
+    }
+
+    /// Parse `input` to [`SignedDuration`], see [`parse_signed`](crate::parse_signed).
+    pub fn parse_signed(&self, input: impl AsRef<str>) -> Result<SignedDuration, DurationError> {
+        parse_nanos(input.as_ref(), self).map(SignedDuration::from_nanos)
+    }
+
+    /// Parse `input` to a [`Span`], see [`parse_span`](crate::parse_span).
+    pub fn parse_span(&self, input: impl AsRef<str>) -> Result<Span, DurationError> {
+        let input = input.as_ref();
+        parse_span_value(input, self)?
+            .round(self)
+            .map_err(|err| DurationError::from_error(input, err))
+    }
+
+    /// Parse `input` to `chrono::Duration`, see [`parse_chrono`](crate::parse_chrono).
+    #[cfg(feature = "chrono")]
+    pub fn parse_chrono(&self, input: impl AsRef<str>) -> Result<chrono::Duration, DurationError> {
+        let input = input.as_ref();
+        let signed_duration = self.parse_signed(input)?;
+        chrono::Duration::try_from(signed_duration)
+            .map_err(|err| DurationError::from_error(input, err))
+    }
+
+    /// Parse `input` to `time::Duration`, see [`parse_time`](crate::parse_time).
+    #[cfg(feature = "time")]
+    pub fn parse_time(&self, input: impl AsRef<str>) -> Result<time::Duration, DurationError> {
+        let input = input.as_ref();
+        let signed_duration = self.parse_signed(input)?;
+        time::Duration::try_from(signed_duration)
+            .map_err(|err| DurationError::from_error(input, err))
+    }
+
+    /// Parse `input` to `jiff::Span`, see [`parse_jiff_span`](crate::parse_jiff_span).
+    #[cfg(feature = "jiff")]
+    pub fn parse_jiff_span(&self, input: impl AsRef<str>) -> Result<jiff::Span, DurationError> {
+        let input = input.as_ref();
+        let span = self.parse_span(input)?;
+        jiff::Span::try_from(span).map_err(|err| DurationError::from_error(input, err))
+    }
+
+    /// Parse `input` to `jiff::SignedDuration`, see [`parse_jiff_signed`](crate::parse_jiff_signed).
+    #[cfg(feature = "jiff")]
+    pub fn parse_jiff_signed(
+        &self,
+        input: impl AsRef<str>,
+    ) -> Result<jiff::SignedDuration, DurationError> {
+        let input = input.as_ref();
+        let signed_duration = self.parse_signed(input)?;
+        jiff::SignedDuration::try_from(signed_duration)
+            .map_err(|err| DurationError::from_error(input, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catch_err;
+
+    #[test]
+    fn test_default_parser() {
+        let parser = DurationParser::default();
+        assert_eq!(parser, DurationParser::new());
+        for input in ["1h 30m", "(1h + 15m) * 3", "01:30:00", "PT1H", "1.5d / 3"] {
+            assert_eq!(parser.parse(input), crate::parse(input), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_case_sensitive() {
+        let parser = DurationParser::new().case_sensitive(true);
+        assert_eq!(parser.parse("1h 30m"), Ok(Duration::new(5400, 0)));
+        assert!(parser.parse("1H").is_err());
+
+        let parser = DurationParser::new().case_sensitive(false);
+        assert_eq!(parser.parse("1H 30MIN"), Ok(Duration::new(5400, 0)));
+    }
+
+    #[test]
+    fn test_calc() {
+        let parser = DurationParser::new().calc(false);
+        assert_eq!(parser.parse("1h 30m 10s"), Ok(Duration::new(5410, 0)));
+        assert!(parser.parse("1h + 30m").is_err());
+        assert!(parser.parse("1h * 2").is_err());
+
+        let parser = DurationParser::new().calc(true);
+        assert_eq!(parser.parse("(1h + 15m) * 3"), Ok(Duration::new(13500, 0)));
+    }
+
+    #[test]
+    fn test_cn_unit() {
+        let parser = DurationParser::new().cn_unit(true);
+        assert_eq!(parser.parse("1天2时"), Ok(Duration::new(93600, 0)));
+        assert_eq!(parser.parse("3毫秒"), Ok(Duration::from_millis(3)));
+
+        let parser = DurationParser::new().cn_unit(false);
+        assert!(parser.parse("1天").is_err());
+    }
+
+    #[test]
+    fn test_default_unit() {
+        let parser = DurationParser::new()
+            .calc(true)
+            .default_unit(TimeUnit::MilliSecond);
+        assert_eq!(parser.parse("500"), Ok(Duration::from_millis(500)));
+        assert_eq!(parser.parse("1s 500"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parser.parse("1m * 10"), Ok(Duration::new(600, 0)));
+        assert_eq!(parser.parse("1s * 2ms"), Ok(Duration::new(2, 0)));
+        assert_eq!(parser.parse("1d / 24"), Ok(Duration::new(3600, 0)));
+
+        let parser = DurationParser::new().default_unit(TimeUnit::Minute);
+        assert_eq!(parser.parse("90"), Ok(Duration::new(5400, 0)));
+    }
+
+    #[test]
+    fn test_strict() {
+        let parser = DurationParser::new().strict(true);
+        assert_eq!(parser.parse("1h 30m"), Ok(Duration::new(5400, 0)));
+        assert_eq!(parser.parse("01:30:00"), Ok(Duration::new(5400, 0)));
+        assert_eq!(
+            catch_err!(parser.parse("500")),
+            r#"
+500
+   ^
+expected ["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"]"#
+                .trim()
+        );
+        assert!(parser.parse("1m 30").is_err());
+        assert!(parser.parse("1m + 30").is_err());
+
+        let parser = parser.calc(true);
+        assert_eq!(parser.parse("1m * 10"), Ok(Duration::new(600, 0)));
+        assert_eq!(parser.parse("1d / (4 * 6)"), Ok(Duration::new(3600, 0)));
+        assert!(parser.parse("10 * 1m").is_err());
+    }
+
+    #[test]
+    fn test_allowed_units() {
+        let parser = DurationParser::new().allowed_units([TimeUnit::Hour, TimeUnit::Minute]);
+        assert_eq!(parser.parse("1h 30m 10"), Ok(Duration::new(5410, 0)));
+        assert_eq!(
+            catch_err!(parser.parse("1h 30s")),
+            r#"
+1h 30s
+     ^
+expected ["h", "m"]"#
+                .trim()
+        );
+
+        let err = parser.parse("1h 30mins").unwrap_err();
+        assert_eq!(err.expected(), ["h", "m"]);
+        assert_eq!(err.suggestion(), Some("min"));
+        let err = parser.parse("1h 30sec").unwrap_err();
+        assert_eq!(err.suggestion(), None);
+    }
+
+    #[test]
+    fn test_month_year_length() {
+        let parser = DurationParser::new()
+            .month_length(Duration::new(2_629_800, 0))
+            .year_length(Duration::new(31_557_600, 0));
+        assert_eq!(parser.parse("1mon"), Ok(Duration::new(2_629_800, 0)));
+        assert_eq!(parser.parse("0.5y"), Ok(Duration::new(15_778_800, 0)));
+        assert_eq!(
+            parser.parse("1y 1mon"),
+            Ok(Duration::new(31_557_600 + 2_629_800, 0))
+        );
+    }
+
+    #[test]
+    fn test_iso8601_settings() {
+        let parser = DurationParser::new()
+            .month_length(Duration::new(31 * 86400, 0))
+            .year_length(Duration::new(366 * 86400, 0));
+        assert_eq!(parser.parse("P1M"), Ok(Duration::new(31 * 86400, 0)));
+        assert_eq!(parser.parse("P1Y"), Ok(Duration::new(366 * 86400, 0)));
+
+        let parser = DurationParser::new().allowed_units([TimeUnit::Hour]);
+        assert_eq!(parser.parse("PT5H"), Ok(Duration::new(5 * 3600, 0)));
+        let err = parser.parse("PT5H5S").unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidUnit);
+        assert_eq!(err.token(), "S");
+        assert_eq!(err.expected(), ["h"]);
+
+        let parser = DurationParser::new().case_sensitive(true);
+        assert_eq!(parser.parse("P1DT1H"), Ok(Duration::new(25 * 3600, 0)));
+        assert!(parser.parse("p1d").is_err());
+        assert!(parser.parse("P1d").is_err());
+        assert!(parser.parse("P1Dt1H").is_err());
+        let parser = DurationParser::new().case_sensitive(false);
+        assert_eq!(parser.parse("p1dt1h"), Ok(Duration::new(25 * 3600, 0)));
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_parse_jiff() {
+        use jiff::ToSpan;
+
+        let parser = DurationParser::new().month_length(Duration::new(31 * 86400, 0));
+        let span = parser.parse_jiff_span("1.5mon 2h").unwrap();
+        assert_eq!(span.fieldwise(), 1.month().days(15).hours(14));
+        assert_eq!(
+            parser.parse_jiff_span("1mon 1x").unwrap_err().kind(),
+            crate::ErrorKind::InvalidUnit
+        );
+
+        let parser = DurationParser::new().strict(true);
+        assert!(parser.parse_jiff_span("10").is_err());
+        assert!(parser.parse_jiff_signed("10").is_err());
+        assert_eq!(
+            parser.parse_jiff_signed("-1.5h"),
+            Ok(jiff::SignedDuration::from_secs(-5400))
+        );
+    }
+}
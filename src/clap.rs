@@ -0,0 +1,307 @@
+use crate::unit::UNIT_ALIASES;
+use crate::{DurationParser, HumanDuration, HumanFormat, TimeUnit};
+use clap::builder::{
+    MapValueParser, PossibleValue, StyledStr, TypedValueParser, ValueParserFactory,
+};
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{Arg, Command, Error};
+use std::ffi::OsStr;
+use std::ops::{Bound, RangeBounds};
+use std::time::Duration;
+
+/// A [clap](https://docs.rs/clap) value parser for `std::time::Duration` arguments such as
+/// `--timeout 1m30s`.
+///
+/// Invalid input is reported as a clap error with a tip from the [`DurationError`], e.g. the
+/// unit it meant. The units are listed as possible values in `--help`; hide them with
+/// `Arg::hide_possible_values`.
+///
+/// # Example
+///
+/// ```rust
+/// use clap::{Arg, Command};
+/// use duration_str::{DurationValueParser, TimeUnit};
+/// use std::time::Duration;
+///
+/// let cmd = Command::new("app").arg(
+///     Arg::new("timeout").long("timeout").value_parser(
+///         DurationValueParser::new()
+///             .default_unit(TimeUnit::MilliSecond)
+///             .range(Duration::from_millis(100)..=Duration::from_secs(300)),
+///     ),
+/// );
+///
+/// let matches = cmd.clone().try_get_matches_from(["app", "--timeout", "1m30s"]).unwrap();
+/// assert_eq!(matches.get_one::<Duration>("timeout"), Some(&Duration::from_secs(90)));
+///
+/// let matches = cmd.clone().try_get_matches_from(["app", "--timeout", "500"]).unwrap();
+/// assert_eq!(matches.get_one::<Duration>("timeout"), Some(&Duration::from_millis(500)));
+///
+/// assert!(cmd.try_get_matches_from(["app", "--timeout", "1h"]).is_err());
+/// ```
+///
+/// [`DurationError`]: crate::DurationError
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DurationValueParser {
+    parser: DurationParser,
+    min: Duration,
+    max: Duration,
+}
+
+impl Default for DurationValueParser {
+    fn default() -> Self {
+        DurationValueParser::new()
+    }
+}
+
+impl DurationValueParser {
+    /// A value parser using [`DurationParser::new`] that accepts any duration.
+    pub const fn new() -> Self {
+        DurationValueParser {
+            parser: DurationParser::new(),
+            min: Duration::ZERO,
+            max: Duration::MAX,
+        }
+    }
+
+    /// Parse with `parser`, e.g. a case sensitive parser or one that only allows some units.
+    pub const fn parser(mut self, parser: DurationParser) -> Self {
+        self.parser = parser;
+        self
+    }
+
+    /// Read bare numbers such as `500` in `unit`. See [`DurationParser::default_unit`].
+    pub const fn default_unit(mut self, unit: TimeUnit) -> Self {
+        self.parser = self.parser.default_unit(unit);
+        self
+    }
+
+    /// Reject durations outside `range`.
+    pub fn range<R: RangeBounds<Duration>>(mut self, range: R) -> Self {
+        self.min = match range.start_bound() {
+            Bound::Included(min) => *min,
+            Bound::Excluded(min) => min.saturating_add(Duration::from_nanos(1)),
+            Bound::Unbounded => Duration::ZERO,
+        };
+        self.max = match range.end_bound() {
+            Bound::Included(max) => *max,
+            Bound::Excluded(max) => max.saturating_sub(Duration::from_nanos(1)),
+            Bound::Unbounded => Duration::MAX,
+        };
+        self
+    }
+
+    fn range_tip(&self) -> String {
+        match (self.min, self.max) {
+            (Duration::ZERO, max) => format!("expected at most {}", max.human_format()),
+            (min, Duration::MAX) => format!("expected at least {}", min.human_format()),
+            (min, max) => format!(
+                "expected between {} and {}",
+                min.human_format(),
+                max.human_format()
+            ),
+        }
+    }
+}
+
+/// A `ValueValidation` error for `value` giving `reason`, styled like clap's own, with `tip`
+/// below it.
+fn invalid_value(
+    cmd: &Command,
+    arg: Option<&Arg>,
+    value: &OsStr,
+    reason: String,
+    tip: Option<String>,
+) -> Error {
+    // clap only attaches a reason to the errors of its closure value parsers
+    let fail = move |_: &str| -> Result<(), String> { Err(reason.clone()) };
+    let mut err = fail.parse_ref(cmd, arg, value).unwrap_err();
+    if let Some(tip) = tip {
+        err.insert(
+            ContextKind::Suggested,
+            ContextValue::StyledStrs(vec![StyledStr::from(tip)]),
+        );
+    }
+    err
+}
+
+impl TypedValueParser for DurationValueParser {
+    type Value = Duration;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let input = value
+            .to_str()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+        let duration = self.parser.parse(input).map_err(|err| {
+            let mut reason = err.to_string();
+            // the caret only lines up below the input if that starts a line
+            if reason.contains('\n') {
+                reason.insert(0, '\n');
+            }
+            invalid_value(cmd, arg, value, reason, err.hint())
+        })?;
+        if duration < self.min || duration > self.max {
+            let reason = "out of range".to_string();
+            return Err(invalid_value(
+                cmd,
+                arg,
+                value,
+                reason,
+                Some(self.range_tip()),
+            ));
+        }
+        Ok(duration)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        let mut units: Vec<TimeUnit> = Vec::new();
+        for (_, unit) in UNIT_ALIASES {
+            if self.parser.allows(unit) && !units.contains(&unit) {
+                units.push(unit);
+            }
+        }
+        Some(Box::new(units.into_iter().map(|unit| {
+            let aliases: Vec<&str> = UNIT_ALIASES
+                .iter()
+                .filter(|(_, u)| *u == unit)
+                .map(|(alias, _)| *alias)
+                .collect();
+            PossibleValue::new(format!("1{}", unit)).help(aliases.join(", "))
+        })))
+    }
+}
+
+/// Lets `#[arg]` fields of type [`HumanDuration`] use [`DurationValueParser`].
+impl ValueParserFactory for HumanDuration {
+    type Parser = MapValueParser<DurationValueParser, fn(Duration) -> HumanDuration>;
+
+    fn value_parser() -> Self::Parser {
+        DurationValueParser::new().map(HumanDuration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(parser: DurationValueParser) -> Command {
+        Command::new("app").arg(Arg::new("timeout").long("timeout").value_parser(parser))
+    }
+
+    fn get(cmd: Command, value: &str) -> Result<Duration, Error> {
+        let matches = cmd.try_get_matches_from(["app", "--timeout", value])?;
+        Ok(*matches.get_one::<Duration>("timeout").unwrap())
+    }
+
+    #[test]
+    fn test_duration_value_parser() {
+        let cmd = command(DurationValueParser::new());
+        assert_eq!(get(cmd.clone(), "1m30s").unwrap(), Duration::from_secs(90));
+        assert_eq!(get(cmd.clone(), "90").unwrap(), Duration::from_secs(90));
+        assert_eq!(get(cmd, "PT1H").unwrap(), Duration::from_secs(3600));
+
+        let cmd = command(DurationValueParser::new().default_unit(TimeUnit::MilliSecond));
+        assert_eq!(get(cmd, "500").unwrap(), Duration::from_millis(500));
+
+        let cmd = command(DurationValueParser::new().parser(DurationParser::new().strict(true)));
+        assert!(get(cmd, "500").is_err());
+    }
+
+    #[test]
+    fn test_duration_value_parser_err() {
+        let cmd = command(DurationValueParser::new());
+        let err = get(cmd, "1hrs").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        let err = err.to_string();
+        assert!(
+            err.starts_with("error: invalid value '1hrs' for '--timeout <timeout>': \n1hrs\n ^\n"),
+            "{err}"
+        );
+        assert!(err.contains("tip: did you mean `hr`?"), "{err}");
+
+        let parser = DurationValueParser::new().parser(DurationParser::new().calc(true));
+        for (value, reason) in [
+            ("1h/0", ": division by zero\n"),
+            ("580y*2", ": overflow error\n"),
+            ("01:75:00", "\nminutes must be less than 60\n"),
+            ("(1h", "\n(1h\n   ^\nexpected `)`\n"),
+        ] {
+            let err = get(command(parser), value).unwrap_err().to_string();
+            assert!(err.contains(reason), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_duration_value_parser_range() {
+        let cmd = command(
+            DurationValueParser::new().range(Duration::from_millis(100)..=Duration::from_secs(300)),
+        );
+        assert_eq!(get(cmd.clone(), "5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(
+            get(cmd.clone(), "100ms").unwrap(),
+            Duration::from_millis(100)
+        );
+        let err = get(cmd.clone(), "5m 1s").unwrap_err().to_string();
+        assert!(
+            err.contains("tip: expected between 100ms and 5min"),
+            "{err}"
+        );
+        assert!(get(cmd, "99ms").is_err());
+
+        let cmd = command(DurationValueParser::new().range(Duration::from_secs(1)..));
+        let err = get(cmd, "1ms").unwrap_err().to_string();
+        assert!(err.contains("tip: expected at least 1s"), "{err}");
+
+        let cmd = command(DurationValueParser::new().range(..Duration::from_secs(1)));
+        assert!(get(cmd.clone(), "999ms").is_ok());
+        let err = get(cmd, "1s").unwrap_err().to_string();
+        assert!(err.contains("tip: expected at most 999ms"), "{err}");
+    }
+
+    #[test]
+    fn test_duration_value_parser_possible_values() {
+        let parser = DurationValueParser::new();
+        let values: Vec<_> = parser
+            .possible_values()
+            .unwrap()
+            .map(|value| value.get_name().to_string())
+            .collect();
+        assert_eq!(
+            values,
+            ["1y", "1mon", "1w", "1d", "1h", "1min", "1s", "1ms", "1µs", "1ns"]
+        );
+
+        let parser = DurationValueParser::new()
+            .parser(DurationParser::new().allowed_units([TimeUnit::Second, TimeUnit::MilliSecond]));
+        let values: Vec<_> = parser.possible_values().unwrap().collect();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].get_name(), "1s");
+        assert_eq!(
+            values[0].get_help().unwrap().to_string(),
+            "s, sec, second, seconds"
+        );
+    }
+
+    #[test]
+    fn test_human_duration_arg() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[arg(long)]
+            timeout: HumanDuration,
+        }
+
+        let cli = Cli::try_parse_from(["app", "--timeout", "1h 30m"]).unwrap();
+        assert_eq!(*cli.timeout, Duration::from_secs(5400));
+        let err = Cli::try_parse_from(["app", "--timeout", "1x"])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    }
+}
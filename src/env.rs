@@ -0,0 +1,252 @@
+//! Read durations from environment variables such as `FOO_TIMEOUT=1m30s`.
+//!
+//! The result type picks the parser: [`parse_std`](crate::parse_std) for
+//! `std::time::Duration`, [`parse_chrono`](crate::parse_chrono) for `chrono::Duration`,
+//! [`parse_time`](crate::parse_time) for `time::Duration` and
+//! [`parse_signed`](crate::parse_signed) for [`SignedDuration`]. An empty value counts as unset.
+//!
+//! # Example
+//!
+//! ```rust
+//! use duration_str::env;
+//! use std::time::Duration;
+//!
+//! std::env::set_var("FOO_TIMEOUT", "1m30s");
+//! let timeout: Duration = env::var("FOO_TIMEOUT").unwrap();
+//! assert_eq!(timeout, Duration::from_secs(90));
+//!
+//! let retry = env::var_or("FOO_RETRY", Duration::from_secs(5)).unwrap();
+//! assert_eq!(retry, Duration::from_secs(5));
+//!
+//! std::env::set_var("FOO_INTERVAL", "1x");
+//! let err = env::var_opt::<Duration>("FOO_INTERVAL").unwrap_err();
+//! assert!(err.to_string().starts_with("environment variable FOO_INTERVAL=\"1x\" is not a valid duration"));
+//! ```
+
+use crate::{DurationError, SignedDuration};
+use std::ffi::OsString;
+use std::time::Duration;
+use thiserror::Error;
+
+/// A duration type that can be read from an environment variable.
+pub trait EnvDuration: Sized {
+    /// Parse the value of an environment variable.
+    fn parse_env(value: &str) -> Result<Self, DurationError>;
+}
+
+impl EnvDuration for Duration {
+    fn parse_env(value: &str) -> Result<Self, DurationError> {
+        crate::parse_std(value)
+    }
+}
+
+impl EnvDuration for SignedDuration {
+    fn parse_env(value: &str) -> Result<Self, DurationError> {
+        crate::parse_signed(value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl EnvDuration for chrono::Duration {
+    fn parse_env(value: &str) -> Result<Self, DurationError> {
+        crate::parse_chrono(value)
+    }
+}
+
+#[cfg(feature = "time")]
+impl EnvDuration for time::Duration {
+    fn parse_env(value: &str) -> Result<Self, DurationError> {
+        crate::parse_time(value)
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl EnvDuration for jiff::SignedDuration {
+    fn parse_env(value: &str) -> Result<Self, DurationError> {
+        crate::parse_jiff_signed(value)
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl EnvDuration for jiff::Span {
+    fn parse_env(value: &str) -> Result<Self, DurationError> {
+        crate::parse_jiff_span(value)
+    }
+}
+
+/// An environment variable that is missing or does not hold a duration.
+#[derive(Error, Debug)]
+pub enum VarError {
+    /// The variable is unset or empty.
+    #[error("environment variable {name} is not set")]
+    NotPresent { name: String },
+    /// The value is not valid unicode.
+    #[error("environment variable {name} is not valid unicode: {value:?}")]
+    NotUnicode { name: String, value: OsString },
+    /// The value does not parse, see `error` for where and why.
+    #[error("environment variable {name}={value:?} is not a valid duration\n{error}")]
+    Invalid {
+        name: String,
+        value: String,
+        error: Box<DurationError>,
+    },
+}
+
+impl VarError {
+    /// The name of the environment variable.
+    pub fn name(&self) -> &str {
+        match self {
+            VarError::NotPresent { name }
+            | VarError::NotUnicode { name, .. }
+            | VarError::Invalid { name, .. } => name,
+        }
+    }
+}
+
+/// Read the duration in the environment variable `name`.
+pub fn var<T: EnvDuration>(name: &str) -> Result<T, VarError> {
+    var_opt(name)?.ok_or_else(|| VarError::NotPresent {
+        name: name.to_string(),
+    })
+}
+
+/// Read the duration in the environment variable `name`, or `default` if it is unset.
+pub fn var_or<T: EnvDuration>(name: &str, default: T) -> Result<T, VarError> {
+    Ok(var_opt(name)?.unwrap_or(default))
+}
+
+/// Read the duration in the environment variable `name`, or `None` if it is unset.
+pub fn var_opt<T: EnvDuration>(name: &str) -> Result<Option<T>, VarError> {
+    let value = match std::env::var(name) {
+        Ok(value) => value,
+        Err(std::env::VarError::NotPresent) => return Ok(None),
+        Err(std::env::VarError::NotUnicode(value)) => {
+            return Err(VarError::NotUnicode {
+                name: name.to_string(),
+                value,
+            })
+        }
+    };
+    if value.trim().is_empty() {
+        return Ok(None);
+    }
+    match T::parse_env(&value) {
+        Ok(duration) => Ok(Some(duration)),
+        Err(error) => Err(VarError::Invalid {
+            name: name.to_string(),
+            value,
+            error: Box::new(error),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn test_var() {
+        std::env::set_var("DURATION_STR_TEST_VAR", "1h 30m");
+        let duration: Duration = var("DURATION_STR_TEST_VAR").unwrap();
+        assert_eq!(duration, Duration::from_secs(5400));
+
+        let err = var::<Duration>("DURATION_STR_TEST_VAR_UNSET").unwrap_err();
+        assert!(matches!(err, VarError::NotPresent { .. }));
+        assert_eq!(err.name(), "DURATION_STR_TEST_VAR_UNSET");
+        assert_eq!(
+            err.to_string(),
+            "environment variable DURATION_STR_TEST_VAR_UNSET is not set"
+        );
+
+        std::env::set_var("DURATION_STR_TEST_VAR_EMPTY", " ");
+        assert!(matches!(
+            var::<Duration>("DURATION_STR_TEST_VAR_EMPTY"),
+            Err(VarError::NotPresent { .. })
+        ));
+    }
+
+    #[test]
+    fn test_var_invalid() {
+        std::env::set_var("DURATION_STR_TEST_VAR_INVALID", "1h 30x");
+        let err = var::<Duration>("DURATION_STR_TEST_VAR_INVALID").unwrap_err();
+        assert_eq!(err.name(), "DURATION_STR_TEST_VAR_INVALID");
+        match &err {
+            VarError::Invalid { value, error, .. } => {
+                assert_eq!(value, "1h 30x");
+                assert_eq!(error.kind(), ErrorKind::InvalidUnit);
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+        assert!(err.to_string().starts_with(
+            "environment variable DURATION_STR_TEST_VAR_INVALID=\"1h 30x\" is not a valid duration\n1h 30x\n     ^"
+        ));
+
+        std::env::set_var("DURATION_STR_TEST_VAR_NEGATIVE", "-1s");
+        let err = var::<Duration>("DURATION_STR_TEST_VAR_NEGATIVE").unwrap_err();
+        assert!(matches!(err, VarError::Invalid { .. }));
+        let duration: SignedDuration = var("DURATION_STR_TEST_VAR_NEGATIVE").unwrap();
+        assert!(duration.is_negative());
+    }
+
+    #[test]
+    fn test_var_or() {
+        std::env::set_var("DURATION_STR_TEST_VAR_OR", "2s");
+        let default = Duration::from_secs(5);
+        assert_eq!(
+            var_or("DURATION_STR_TEST_VAR_OR", default).unwrap(),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            var_or("DURATION_STR_TEST_VAR_OR_UNSET", default).unwrap(),
+            default
+        );
+
+        std::env::set_var("DURATION_STR_TEST_VAR_OR_INVALID", "2x");
+        assert!(var_or("DURATION_STR_TEST_VAR_OR_INVALID", default).is_err());
+    }
+
+    #[test]
+    fn test_var_opt() {
+        std::env::set_var("DURATION_STR_TEST_VAR_OPT", "500ms");
+        assert_eq!(
+            var_opt("DURATION_STR_TEST_VAR_OPT").unwrap(),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            var_opt::<Duration>("DURATION_STR_TEST_VAR_OPT_UNSET").unwrap(),
+            None
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_var_chrono() {
+        std::env::set_var("DURATION_STR_TEST_VAR_CHRONO", "-1h");
+        let duration: chrono::Duration = var("DURATION_STR_TEST_VAR_CHRONO").unwrap();
+        assert_eq!(duration, chrono::Duration::hours(-1));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_var_time() {
+        std::env::set_var("DURATION_STR_TEST_VAR_TIME", "1d");
+        let duration: time::Duration = var("DURATION_STR_TEST_VAR_TIME").unwrap();
+        assert_eq!(duration, time::Duration::days(1));
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_var_jiff() {
+        use jiff::ToSpan;
+
+        std::env::set_var("DURATION_STR_TEST_VAR_JIFF", "1y 6mon");
+        let span: jiff::Span = var("DURATION_STR_TEST_VAR_JIFF").unwrap();
+        assert_eq!(span.fieldwise(), 1.year().months(6));
+        let duration: jiff::SignedDuration = var("DURATION_STR_TEST_VAR_JIFF").unwrap();
+        assert_eq!(
+            duration,
+            jiff::SignedDuration::from_hours(365 * 24 + 180 * 24)
+        );
+    }
+}
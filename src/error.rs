@@ -1,5 +1,9 @@
-use std::fmt::Debug;
+use crate::unit::TimeUnit;
+use crate::{CondUnit, DurationParser, ExpectErr, MINUS, PLUS, SLASH, STAR};
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Range;
 use thiserror::Error;
+use winnow::error::{ContextError, ParseError, StrContext, StrContextValue};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum DError {
@@ -7,4 +11,498 @@ pub enum DError {
     ParseError(String),
     #[error("overflow error")]
     OverflowError,
+    #[error("negative duration is not supported by std::time::Duration")]
+    NegativeError,
+    #[error("division by zero")]
+    DivisionByZero,
+}
+
+/// What went wrong in a [`DurationError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The input is empty.
+    Empty,
+    /// A unit is unknown or not allowed, e.g. the `x` in `1x`.
+    InvalidUnit,
+    /// A number has no unit in strict mode.
+    MissingUnit,
+    /// An operator was expected, e.g. the `%` in `3ms%2ms`.
+    InvalidOperator,
+    /// A `(` is never closed.
+    UnclosedParen,
+    /// A clock time such as `01:75:00` is malformed or out of range.
+    InvalidClock,
+    /// An ISO 8601 duration such as `P1H` is malformed.
+    InvalidIso8601,
+    /// Any other syntax error, e.g. a missing operand in `1h *`.
+    Syntax,
+    /// The value does not fit into the target type.
+    Overflow,
+    /// The value is negative but the target type is unsigned.
+    Negative,
+    /// An expression divides by zero.
+    DivisionByZero,
+}
+
+/// A parse error with the location of the offending part of the input.
+///
+/// `Display` prints the input with a caret under the error, followed by what was expected:
+///
+/// ```text
+/// 1h 30x
+///      ^
+/// expected ["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"]
+/// ```
+///
+/// A misspelled unit such as `minuts` adds the closest unit name on a last line, here
+/// ``did you mean `minute`?``.
+///
+/// Errors found after parsing, such as an overflow, span the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DurationError {
+    kind: ErrorKind,
+    input: String,
+    span: Range<usize>,
+    expected: Vec<String>,
+    suggestion: Option<&'static str>,
+    message: String,
+}
+
+impl DurationError {
+    /// What went wrong.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The whole input that failed to parse.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The byte range of the offending token in [`input`](Self::input); empty at the end of
+    /// the input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The offending part of the input, e.g. `x` in `1x`.
+    pub fn token(&self) -> &str {
+        &self.input[self.span.clone()]
+    }
+
+    /// What would have been accepted at [`span`](Self::span), e.g. the unit names.
+    pub fn expected(&self) -> &[String] {
+        &self.expected
+    }
+
+    /// The unit name closest to a misspelled unit, e.g. `minute` for `minuts`.
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion
+    }
+
+    pub(crate) fn empty() -> Self {
+        DurationError {
+            kind: ErrorKind::Empty,
+            input: String::new(),
+            span: 0..0,
+            expected: Vec::new(),
+            suggestion: None,
+            message: String::from("Empty input"),
+        }
+    }
+
+    /// An error from one of the hand-written parsers such as [`parse_go`](crate::parse_go),
+    /// which keep their own messages.
+    pub(crate) fn new(
+        kind: ErrorKind,
+        input: &str,
+        span: Range<usize>,
+        expected: Vec<String>,
+        message: String,
+    ) -> Self {
+        DurationError {
+            kind,
+            input: input.to_string(),
+            span,
+            expected,
+            suggestion: None,
+            message,
+        }
+    }
+
+    /// An error found after parsing, spanning the whole input.
+    pub(crate) fn from_error(input: &str, err: DError) -> Self {
+        let kind = match err {
+            DError::OverflowError => ErrorKind::Overflow,
+            DError::NegativeError => ErrorKind::Negative,
+            DError::DivisionByZero => ErrorKind::DivisionByZero,
+            DError::ParseError(_) => ErrorKind::Syntax,
+        };
+        DurationError {
+            kind,
+            input: input.to_string(),
+            span: 0..input.len(),
+            expected: Vec::new(),
+            suggestion: None,
+            message: err.to_string(),
+        }
+    }
+
+    /// Convert a winnow error, classifying it by the context of the failed parser.
+    pub(crate) fn from_parse_error(err: ParseError<&str, ContextError>) -> Self {
+        let input = *err.input();
+        let offset = err.offset();
+        let mut span = offset..offset + token_len(&input[offset..]);
+
+        let mut kind = None;
+        let mut expected = Vec::new();
+        for context in err.inner().context() {
+            let StrContext::Expected(value) = context else {
+                continue;
+            };
+            let value_kind = match value {
+                StrContextValue::Description(desc) if *desc == TimeUnit::get_expect_val() => {
+                    expected.extend(TimeUnit::expect_val().map(String::from));
+                    // only a word is an attempt at a unit, e.g. `x` but not `+` in `500 + 1s`
+                    if input[offset..].starts_with(char::is_alphabetic) {
+                        ErrorKind::InvalidUnit
+                    } else {
+                        let end = input[..offset].trim_end().len();
+                        span = end..end;
+                        ErrorKind::MissingUnit
+                    }
+                }
+                StrContextValue::Description(desc) if *desc == CondUnit::get_expect_val() => {
+                    expected.extend([PLUS, MINUS, STAR, SLASH].map(String::from));
+                    ErrorKind::InvalidOperator
+                }
+                StrContextValue::Description(desc) if *desc == crate::parser::EXPECT_CLOCK => {
+                    expected.push(desc.to_string());
+                    ErrorKind::InvalidClock
+                }
+                StrContextValue::Description(desc)
+                    if *desc == crate::iso8601::EXPECT_DESIGNATOR =>
+                {
+                    expected.push(desc.to_string());
+                    ErrorKind::InvalidIso8601
+                }
+                StrContextValue::CharLiteral(')') => {
+                    expected.push(")".to_string());
+                    ErrorKind::UnclosedParen
+                }
+                value => {
+                    expected.push(value.to_string());
+                    ErrorKind::Syntax
+                }
+            };
+            kind.get_or_insert(value_kind);
+        }
+        let kind = match err.inner().cause().and_then(|cause| cause.downcast_ref()) {
+            Some(DError::OverflowError) => ErrorKind::Overflow,
+            _ => kind.unwrap_or(ErrorKind::Syntax),
+        };
+
+        DurationError {
+            kind,
+            input: input.to_string(),
+            span,
+            expected,
+            suggestion: None,
+            message: err.to_string(),
+        }
+    }
+
+    /// Suggest the closest unit name `parser` accepts for an invalid unit, and only list the
+    /// units it accepts as expected.
+    pub(crate) fn suggest_unit(mut self, parser: &DurationParser) -> Self {
+        if matches!(self.kind, ErrorKind::InvalidUnit | ErrorKind::MissingUnit) {
+            let allowed: Vec<&str> = TimeUnit::expect_val()
+                .into_iter()
+                .filter(|name| {
+                    TimeUnit::from_alias(name, true, false).is_some_and(|unit| parser.allows(unit))
+                })
+                .collect();
+            self.message = self
+                .message
+                .replace(TimeUnit::get_expect_val(), &format!("{:?}", allowed));
+            self.expected = allowed.into_iter().map(String::from).collect();
+        }
+        if self.kind == ErrorKind::InvalidUnit {
+            let token = self.token();
+            let name = &token[..token.find(char::is_numeric).unwrap_or(token.len())];
+            self.suggestion = TimeUnit::suggest(name, parser);
+            if let Some(alias) = self.suggestion {
+                self.message
+                    .push_str(&format!("\ndid you mean `{}`?", alias));
+            }
+        }
+        self
+    }
+
+    /// How to fix the input: the valid units or operators, or what was expected.
+    #[cfg(any(feature = "miette", feature = "clap"))]
+    pub(crate) fn hint(&self) -> Option<String> {
+        let help = match self.kind {
+            ErrorKind::InvalidUnit | ErrorKind::MissingUnit => {
+                let units = self.expected.join(", ");
+                match self.suggestion {
+                    Some(alias) => format!("did you mean `{}`? valid units are {}", alias, units),
+                    None => format!("valid units are {}", units),
+                }
+            }
+            ErrorKind::InvalidOperator => {
+                format!(
+                    "valid operators are {}",
+                    [PLUS, MINUS, STAR, SLASH].join(", ")
+                )
+            }
+            ErrorKind::Negative => "the duration must not be negative".to_string(),
+            _ if self.expected.is_empty() => return None,
+            _ => format!("expected {}", self.expected.join(" or ")),
+        };
+        Some(help)
+    }
+}
+
+/// Length of the token starting `rest`: a run of word characters such as `nys` or `01:75:00`,
+/// or a single other character.
+pub(crate) fn token_len(rest: &str) -> usize {
+    let is_word = |c: char| c.is_alphanumeric() || matches!(c, 'µ' | '.' | ':');
+    match rest.chars().next() {
+        None => 0,
+        Some(c) if is_word(c) => rest.find(|c| !is_word(c)).unwrap_or(rest.len()),
+        Some(c) => c.len_utf8(),
+    }
+}
+
+impl Display for DurationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for DurationError {}
+
+/// Rich reports for [miette](https://docs.rs/miette): the offending token is labelled in the
+/// input, the help lists the valid units or operators and every [`ErrorKind`] has its own code,
+/// e.g. `duration_str::invalid_unit`.
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for DurationError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self.kind {
+            ErrorKind::Empty => "duration_str::empty",
+            ErrorKind::InvalidUnit => "duration_str::invalid_unit",
+            ErrorKind::MissingUnit => "duration_str::missing_unit",
+            ErrorKind::InvalidOperator => "duration_str::invalid_operator",
+            ErrorKind::UnclosedParen => "duration_str::unclosed_paren",
+            ErrorKind::InvalidClock => "duration_str::invalid_clock",
+            ErrorKind::InvalidIso8601 => "duration_str::invalid_iso8601",
+            ErrorKind::Syntax => "duration_str::syntax",
+            ErrorKind::Overflow => "duration_str::overflow",
+            ErrorKind::Negative => "duration_str::negative",
+            ErrorKind::DivisionByZero => "duration_str::division_by_zero",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.hint()
+            .map(|help| Box::new(help) as Box<dyn Display + 'a>)
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.input)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let label = match self.kind {
+            ErrorKind::Empty => return None,
+            ErrorKind::InvalidUnit => "unknown unit",
+            ErrorKind::MissingUnit => "missing unit",
+            ErrorKind::InvalidOperator => "expected an operator",
+            ErrorKind::UnclosedParen => "expected `)`",
+            ErrorKind::InvalidClock => "invalid clock time",
+            ErrorKind::InvalidIso8601 => "invalid ISO 8601 duration",
+            ErrorKind::Syntax => "unexpected input",
+            ErrorKind::Overflow => "out of range",
+            ErrorKind::Negative => "negative duration",
+            ErrorKind::DivisionByZero => "division by zero",
+        };
+        let span = miette::LabeledSpan::new_primary_with_span(Some(label.to_string()), self.span());
+        Some(Box::new(std::iter::once(span)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_duration_error_unit() {
+        let err = parse("1h 30x").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidUnit);
+        assert_eq!(err.input(), "1h 30x");
+        assert_eq!(err.span(), 5..6);
+        assert_eq!(err.token(), "x");
+        assert_eq!(
+            err.expected(),
+            ["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"]
+        );
+
+        let err = parse("3 hourz").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidUnit);
+        assert_eq!(err.span(), 2..7);
+        assert_eq!(err.token(), "hourz");
+
+        let err = crate::DurationParser::new()
+            .strict(true)
+            .parse("1h 30")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingUnit);
+        assert_eq!(err.span(), 5..5);
+        assert_eq!(err.token(), "");
+
+        for (input, end) in [("500 + 1s", 3), ("(10)", 3), ("1h 30 ", 5)] {
+            let err = crate::DurationParser::new()
+                .strict(true)
+                .calc(true)
+                .parse(input)
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::MissingUnit, "{input}");
+            assert_eq!(err.span(), end..end, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_duration_error_suggestion() {
+        let cases = [
+            ("1 minuts", Some("minute")),
+            ("2secs", Some("sec")),
+            ("3hrs", Some("hr")),
+            ("4 mins", Some("min")),
+            ("5mili", Some("millisecond")),
+            ("6dya", Some("day")),
+            ("7x", None),
+            ("8fortnight", None),
+        ];
+        for (input, suggestion) in cases {
+            let err = parse(input).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidUnit, "{input}");
+            assert_eq!(err.suggestion(), suggestion, "{input}");
+        }
+
+        let err = parse("1h 30minuts").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"
+1h 30minuts
+     ^
+expected ["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"]
+did you mean `minute`?"#
+                .trim()
+        );
+
+        let parser = crate::DurationParser::new().allowed_units([TimeUnit::Second]);
+        assert_eq!(parser.parse("1secs").unwrap_err().suggestion(), Some("sec"));
+        assert_eq!(parser.parse("1mins").unwrap_err().suggestion(), None);
+
+        let parser = crate::DurationParser::new().case_sensitive(true);
+        assert_eq!(parser.parse("1Hrs").unwrap_err().suggestion(), Some("hr"));
+
+        let parser = crate::DurationParser::new().cn_unit(true);
+        assert_eq!(parser.parse("1毫").unwrap_err().suggestion(), Some("毫秒"));
+    }
+
+    #[cfg(not(feature = "no_calc"))]
+    #[test]
+    fn test_duration_error_expr() {
+        let err = parse("3ms%2ms").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidOperator);
+        assert_eq!(err.span(), 3..4);
+        assert_eq!(err.expected(), ["+", "-", "*", "/"]);
+
+        let err = parse("(1h + 15m").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnclosedParen);
+        assert_eq!(err.span(), 9..9);
+        assert_eq!(err.expected(), [")"]);
+
+        let err = parse("1h * ").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Syntax);
+
+        let err = parse("1h / (1m - 60s)").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DivisionByZero);
+        assert_eq!(err.span(), 0..15);
+        assert_eq!(err.to_string(), "division by zero");
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn test_diagnostic() {
+        use miette::{Diagnostic, LabeledSpan};
+
+        let err = parse("1h 30hrz").unwrap_err();
+        assert_eq!(
+            err.code().unwrap().to_string(),
+            "duration_str::invalid_unit"
+        );
+        assert_eq!(
+            err.help().unwrap().to_string(),
+            "did you mean `hr`? valid units are y, mon, w, d, h, m, s, ms, µs, us, ns"
+        );
+        assert_eq!(
+            err.labels().unwrap().collect::<Vec<_>>(),
+            [LabeledSpan::new_primary_with_span(
+                Some("unknown unit".to_string()),
+                5..8
+            )]
+        );
+        assert!(err.source_code().is_some());
+
+        let err = parse("-1h").unwrap_err();
+        assert_eq!(err.code().unwrap().to_string(), "duration_str::negative");
+        assert_eq!(
+            err.help().unwrap().to_string(),
+            "the duration must not be negative"
+        );
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!((label.offset(), label.len()), (0, 3));
+
+        let err = parse("").unwrap_err();
+        assert_eq!(err.code().unwrap().to_string(), "duration_str::empty");
+        assert!(err.labels().is_none());
+        assert!(err.help().is_none());
+    }
+
+    #[test]
+    fn test_duration_error_kind() {
+        assert_eq!(parse("").unwrap_err().kind(), ErrorKind::Empty);
+        assert_eq!(parse("-3ms").unwrap_err().kind(), ErrorKind::Negative);
+        assert_eq!(parse("585y").unwrap_err().kind(), ErrorKind::Overflow);
+        assert_eq!(
+            parse("99999999999999999999999999999h").unwrap_err().kind(),
+            ErrorKind::Overflow
+        );
+        assert_eq!(
+            parse("1000000000000000000000000000000000s")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Overflow
+        );
+        assert_eq!(
+            crate::parse_signed("100000000000000000000y")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Overflow
+        );
+
+        let err = parse("01:75:00").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidClock);
+        assert_eq!(err.token(), "01:75:00");
+
+        let err = parse("P1H").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidIso8601);
+        assert_eq!(err.span(), 1..3);
+    }
 }
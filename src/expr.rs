@@ -0,0 +1,142 @@
+use crate::span::SpanValue;
+use crate::unit::TimeUnit;
+use crate::{CondUnit, DError, DResult, DurationParser};
+use rust_decimal::Decimal;
+
+/// Abstract syntax tree of a duration expression such as `(1h + 15m) * 3`.
+///
+/// Values are kept as exact nanosecond amounts; the result is only rounded
+/// once the whole tree has been evaluated.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr {
+    /// A quantity with its unit applied, in nanoseconds.
+    Value(Decimal),
+    /// A quantity of a calendar unit and its length in nanoseconds, so spans can keep the unit.
+    Calendar(Decimal, TimeUnit, Decimal),
+    /// Unary minus.
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, CondUnit, Box<Expr>),
+}
+
+impl Expr {
+    pub(crate) fn binary(lhs: Expr, op: CondUnit, rhs: Expr) -> Self {
+        Expr::Binary(Box::new(lhs), op, Box::new(rhs))
+    }
+
+    /// Evaluate the expression with checked arithmetic.
+    ///
+    /// `scalar` is the length of the default unit, which `*` and `/` count their operands in.
+    pub(crate) fn eval(&self, scalar: Decimal) -> DResult<Decimal> {
+        match self {
+            Expr::Value(nanos) | Expr::Calendar(_, _, nanos) => Ok(*nanos),
+            Expr::Neg(expr) => Ok(-expr.eval(scalar)?),
+            Expr::Binary(lhs, op, rhs) => op.calc(lhs.eval(scalar)?, rhs.eval(scalar)?, scalar),
+        }
+    }
+
+    /// Evaluate the expression like [`Expr::eval`], but keep calendar units apart.
+    ///
+    /// `*` scales the operand with calendar units by the other one, counted in the default
+    /// unit like in [`Expr::eval`]; `/` divides by its right operand.
+    pub(crate) fn eval_span(&self, parser: &DurationParser) -> DResult<SpanValue> {
+        match self {
+            Expr::Value(nanos) => Ok(SpanValue::from_nanos(*nanos)),
+            Expr::Calendar(quantity, unit, _) => SpanValue::from_quantity(*unit, *quantity, parser),
+            Expr::Neg(expr) => Ok(-expr.eval_span(parser)?),
+            Expr::Binary(lhs, op, rhs) => {
+                let (lhs, rhs) = (lhs.eval_span(parser)?, rhs.eval_span(parser)?);
+                let scalar = parser.unit_nanos(parser.default_unit);
+                match op {
+                    CondUnit::Plus => lhs.checked_add(&rhs),
+                    CondUnit::Minus => lhs.checked_add(&-rhs),
+                    CondUnit::Star => {
+                        let (span, count) = if lhs.is_time() {
+                            (rhs, lhs)
+                        } else {
+                            (lhs, rhs)
+                        };
+                        span.checked_mul(count.flatten(parser)? / scalar)
+                    }
+                    CondUnit::Slash => {
+                        let count = rhs.flatten(parser)?;
+                        if count.is_zero() {
+                            return Err(DError::DivisionByZero);
+                        }
+                        lhs.checked_mul(scalar.checked_div(count).ok_or(DError::OverflowError)?)
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ONE_MINUTE_NANOSECOND, ONE_SECOND_NANOSECOND};
+
+    fn minutes(n: u64) -> Expr {
+        Expr::Value((n * ONE_MINUTE_NANOSECOND).into())
+    }
+
+    fn second() -> Decimal {
+        ONE_SECOND_NANOSECOND.into()
+    }
+
+    fn scalar(n: u64) -> Expr {
+        Expr::Value((n * ONE_SECOND_NANOSECOND).into())
+    }
+
+    #[test]
+    fn test_eval() {
+        // (60m + 15m) * 3
+        let expr = Expr::binary(
+            Expr::binary(minutes(60), CondUnit::Plus, minutes(15)),
+            CondUnit::Star,
+            scalar(3),
+        );
+        assert_eq!(
+            expr.eval(second()),
+            Ok((225 * ONE_MINUTE_NANOSECOND).into())
+        );
+
+        // -(10m - 60m)
+        let expr = Expr::Neg(Box::new(Expr::binary(
+            minutes(10),
+            CondUnit::Minus,
+            minutes(60),
+        )));
+        assert_eq!(expr.eval(second()), Ok((50 * ONE_MINUTE_NANOSECOND).into()));
+
+        // 60m / 7 stays exact until rounded
+        let expr = Expr::binary(minutes(60), CondUnit::Slash, scalar(7));
+        let nanos = expr.eval(second()).unwrap();
+        assert_eq!(
+            crate::round_nanos(nanos),
+            Ok(514_285_714_286),
+            "60m / 7 = 514.285714285714...s"
+        );
+    }
+
+    #[test]
+    fn test_eval_overflow() {
+        let expr = Expr::binary(Expr::Value(Decimal::MAX), CondUnit::Plus, minutes(1));
+        assert_eq!(expr.eval(second()), Err(DError::OverflowError));
+
+        let expr = Expr::binary(Expr::Value(Decimal::MAX), CondUnit::Star, scalar(2));
+        assert_eq!(expr.eval(second()), Err(DError::OverflowError));
+    }
+
+    #[test]
+    fn test_eval_division_by_zero() {
+        let expr = Expr::binary(minutes(1), CondUnit::Slash, scalar(0));
+        assert_eq!(expr.eval(second()), Err(DError::DivisionByZero));
+
+        let expr = Expr::binary(
+            minutes(1),
+            CondUnit::Slash,
+            Expr::binary(minutes(1), CondUnit::Minus, scalar(60)),
+        );
+        assert_eq!(expr.eval(second()), Err(DError::DivisionByZero));
+    }
+}
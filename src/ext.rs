@@ -1,10 +1,46 @@
+#[cfg(feature = "jiff")]
+use crate::systemd::systemd_nanos;
+use crate::systemd::{SYSTEMD_MONTH_NANOSECOND, SYSTEMD_YEAR_NANOSECOND};
 use crate::unit::TimeUnit;
+use crate::ONE_SECOND_NANOSECOND;
 use std::time::Duration;
 
 pub trait HumanFormat {
     fn human_format(&self) -> String;
 }
 
+/// Formatting like systemd's time spans.
+pub trait SystemdFormat {
+    /// Format like systemd's `format_timespan` with microsecond accuracy, e.g. `1h 30min` or
+    /// `1.500000s`.
+    ///
+    /// A month is 30.44 days and a year 365.25 days, and zero is written as `0`, so the output
+    /// parses back with [`parse_systemd`](crate::parse_systemd). Nanoseconds are truncated.
+    /// systemd has no negative time spans: every term of a negative duration gets its own `-`,
+    /// e.g. `-1h -30min`, which [`parse_signed`](crate::parse_signed) reads back but
+    /// `parse_systemd` rejects.
+    fn systemd_format(&self) -> String;
+}
+
+/// Formatting like Go's `time.Duration`.
+pub trait GoFormat {
+    /// Format exactly like Go's `time.Duration.String()`, e.g. `1h15m30.5s` or `1.5µs`.
+    ///
+    /// Hours are the largest unit and zero is written as `0s`, so the output always parses back
+    /// with [`parse_go`](crate::parse_go) as long as it fits in Go's `int64` nanoseconds.
+    fn go_format(&self) -> String;
+}
+
+/// Formatting as an ISO 8601 duration.
+pub trait Iso8601Format {
+    /// Format as a canonical ISO 8601 duration such as `P1Y2M3DT4H5M6.789S`.
+    ///
+    /// Years and months are 365 and 30 days, so the output always parses back to the same
+    /// value with [`parse_iso8601`](crate::parse_iso8601). Weeks are folded into days and a zero
+    /// duration is written as `PT0S`.
+    fn iso8601_format(&self) -> String;
+}
+
 const ONE_SECOND_SECOND: u64 = 1;
 const ONE_MINUTE_SECOND: u64 = 60 * ONE_SECOND_SECOND;
 const ONE_HOUR_SECOND: u64 = 60 * ONE_MINUTE_SECOND;
@@ -56,6 +92,156 @@ fn format_inner(seconds: u64, nanos: u32) -> String {
     format
 }
 
+/// [`format_inner`] for signed durations: every term of a negative duration gets its own `-`,
+/// e.g. `-1h -30min`, so the output parses back to the same value.
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+fn signed_format_inner(negative: bool, seconds: u64, nanos: u32) -> String {
+    let format = format_inner(seconds, nanos);
+    if !negative {
+        return format;
+    }
+    negate_terms(&format)
+}
+
+/// Put a `-` before every space separated term of `format`.
+fn negate_terms(format: &str) -> String {
+    format
+        .split(' ')
+        .map(|term| format!("-{}", term))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn iso8601_format_inner(negative: bool, seconds: u64, nanos: u32) -> String {
+    if seconds == 0 && nanos == 0 {
+        return "PT0S".to_string();
+    }
+
+    let year = seconds / ONE_YEAR_SECOND;
+    let ydays = seconds % ONE_YEAR_SECOND;
+    let month = ydays / ONE_MONTH_SECOND;
+    let mdays = ydays % ONE_MONTH_SECOND;
+    let day = mdays / ONE_DAY_SECOND;
+    let day_secs = mdays % ONE_DAY_SECOND;
+    let hour = day_secs / ONE_HOUR_SECOND;
+    let minutes = day_secs % ONE_HOUR_SECOND / ONE_MINUTE_SECOND;
+    let second = day_secs % ONE_MINUTE_SECOND;
+
+    let mut format = String::from(if negative { "-P" } else { "P" });
+    for (val, designator) in [(year, 'Y'), (month, 'M'), (day, 'D')] {
+        if val > 0 {
+            format.push_str(&format!("{}{}", val, designator));
+        }
+    }
+    if hour > 0 || minutes > 0 || second > 0 || nanos > 0 {
+        format.push('T');
+        for (val, designator) in [(hour, 'H'), (minutes, 'M')] {
+            if val > 0 {
+                format.push_str(&format!("{}{}", val, designator));
+            }
+        }
+        if nanos > 0 {
+            let fraction = format!("{:09}", nanos);
+            format.push_str(&format!("{}.{}S", second, fraction.trim_end_matches('0')));
+        } else if second > 0 {
+            format.push_str(&format!("{}S", second));
+        }
+    }
+    format
+}
+
+/// Port of Go's `Duration.format`, with `nanos` being the absolute value.
+fn go_format_inner(negative: bool, nanos: u128) -> String {
+    // Append `.fraction` of `value` with `prec` digits, omitting trailing zeros.
+    fn push_frac(format: &mut String, value: u128, prec: usize) {
+        let fraction = format!("{:0prec$}", value, prec = prec);
+        let fraction = fraction.trim_end_matches('0');
+        if !fraction.is_empty() {
+            format.push('.');
+            format.push_str(fraction);
+        }
+    }
+
+    let mut format = String::from(if negative { "-" } else { "" });
+    if nanos < ONE_SECOND_NANOSECOND as u128 {
+        // smaller than a second: use smaller units, like 1.2ms
+        let (unit, prec) = match nanos {
+            0 => return "0s".to_string(),
+            1..=999 => ("ns", 0),
+            1_000..=999_999 => ("µs", 3),
+            _ => ("ms", 6),
+        };
+        let scale = 10u128.pow(prec as u32);
+        format.push_str(&(nanos / scale).to_string());
+        push_frac(&mut format, nanos % scale, prec);
+        format.push_str(unit);
+        return format;
+    }
+
+    let seconds = nanos / ONE_SECOND_NANOSECOND as u128;
+    let hour = seconds / ONE_HOUR_SECOND as u128;
+    let minutes = seconds % ONE_HOUR_SECOND as u128 / ONE_MINUTE_SECOND as u128;
+    // stop at hours because days can be different lengths
+    if hour > 0 {
+        format.push_str(&format!("{}h", hour));
+    }
+    if seconds >= ONE_MINUTE_SECOND as u128 {
+        format.push_str(&format!("{}m", minutes));
+    }
+    format.push_str(&(seconds % ONE_MINUTE_SECOND as u128).to_string());
+    push_frac(&mut format, nanos % ONE_SECOND_NANOSECOND as u128, 9);
+    format.push('s');
+    format
+}
+
+/// Port of systemd's `format_timespan` with an accuracy of one microsecond, with `nanos` being
+/// the absolute value.
+fn systemd_format_inner(negative: bool, nanos: u128) -> String {
+    const TABLE: [(&str, u64); 9] = [
+        ("y", SYSTEMD_YEAR_NANOSECOND / 1000),
+        ("month", SYSTEMD_MONTH_NANOSECOND / 1000),
+        ("w", ONE_WEEK_SECOND * 1_000_000),
+        ("d", ONE_DAY_SECOND * 1_000_000),
+        ("h", ONE_HOUR_SECOND * 1_000_000),
+        ("min", ONE_MINUTE_SECOND * 1_000_000),
+        ("s", 1_000_000),
+        ("ms", 1000),
+        ("us", 1),
+    ];
+
+    let mut t = nanos / 1000;
+    if t == 0 {
+        return "0".to_string();
+    }
+    let mut format = String::new();
+    for (suffix, usec) in TABLE {
+        let usec = usec as u128;
+        if t == 0 {
+            break;
+        }
+        if t < usec {
+            continue;
+        }
+        if !format.is_empty() {
+            format.push(' ');
+        }
+        let (a, b) = (t / usec, t % usec);
+        // show seconds and below in dot notation
+        let digits = usec.ilog10() as usize;
+        if t < ONE_MINUTE_SECOND as u128 * 1_000_000 && b > 0 && digits > 0 {
+            format.push_str(&format!("{}.{:0digits$}{}", a, b, suffix, digits = digits));
+            t = 0;
+        } else {
+            format.push_str(&format!("{}{}", a, suffix));
+            t = b;
+        }
+    }
+    if negative {
+        return negate_terms(&format);
+    }
+    format
+}
+
 impl HumanFormat for Duration {
     fn human_format(&self) -> String {
         let seconds = self.as_secs();
@@ -64,27 +250,217 @@ impl HumanFormat for Duration {
     }
 }
 
-#[cfg(all(feature = "chrono", feature = "serde"))]
+impl SystemdFormat for Duration {
+    fn systemd_format(&self) -> String {
+        if *self == Duration::MAX {
+            return "infinity".to_string();
+        }
+        systemd_format_inner(false, self.as_nanos())
+    }
+}
+
+impl GoFormat for Duration {
+    fn go_format(&self) -> String {
+        go_format_inner(false, self.as_nanos())
+    }
+}
+
+impl Iso8601Format for Duration {
+    fn iso8601_format(&self) -> String {
+        iso8601_format_inner(false, self.as_secs(), self.subsec_nanos())
+    }
+}
+
+#[cfg(feature = "chrono")]
 use chrono::Duration as CDuration;
 
-#[cfg(all(feature = "time", feature = "serde"))]
+#[cfg(feature = "time")]
 use time::Duration as TDuration;
 
-#[cfg(all(feature = "chrono", feature = "serde"))]
+#[cfg(feature = "chrono")]
+fn chrono_abs_nanos(duration: &CDuration) -> u128 {
+    duration.num_seconds().unsigned_abs() as u128 * ONE_SECOND_NANOSECOND as u128
+        + duration.subsec_nanos().unsigned_abs() as u128
+}
+
+#[cfg(feature = "chrono")]
 impl HumanFormat for CDuration {
     fn human_format(&self) -> String {
-        let seconds = self.num_seconds() as _;
-        let nanos = self.subsec_nanos() as _;
-        format_inner(seconds, nanos)
+        signed_format_inner(
+            *self < CDuration::zero(),
+            self.num_seconds().unsigned_abs(),
+            self.subsec_nanos().unsigned_abs(),
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl SystemdFormat for CDuration {
+    fn systemd_format(&self) -> String {
+        systemd_format_inner(*self < CDuration::zero(), chrono_abs_nanos(self))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl GoFormat for CDuration {
+    fn go_format(&self) -> String {
+        go_format_inner(*self < CDuration::zero(), chrono_abs_nanos(self))
     }
 }
 
-#[cfg(all(feature = "time", feature = "serde"))]
+#[cfg(feature = "chrono")]
+impl Iso8601Format for CDuration {
+    fn iso8601_format(&self) -> String {
+        iso8601_format_inner(
+            *self < CDuration::zero(),
+            self.num_seconds().unsigned_abs(),
+            self.subsec_nanos().unsigned_abs(),
+        )
+    }
+}
+
+#[cfg(feature = "time")]
 impl HumanFormat for TDuration {
     fn human_format(&self) -> String {
-        let seconds = self.as_seconds_f64() as _;
-        let nanos = self.subsec_nanoseconds() as _;
-        format_inner(seconds, nanos)
+        signed_format_inner(
+            self.is_negative(),
+            self.whole_seconds().unsigned_abs(),
+            self.subsec_nanoseconds().unsigned_abs(),
+        )
+    }
+}
+
+#[cfg(feature = "time")]
+impl SystemdFormat for TDuration {
+    fn systemd_format(&self) -> String {
+        systemd_format_inner(self.is_negative(), self.whole_nanoseconds().unsigned_abs())
+    }
+}
+
+#[cfg(feature = "time")]
+impl GoFormat for TDuration {
+    fn go_format(&self) -> String {
+        go_format_inner(self.is_negative(), self.whole_nanoseconds().unsigned_abs())
+    }
+}
+
+#[cfg(feature = "time")]
+impl Iso8601Format for TDuration {
+    fn iso8601_format(&self) -> String {
+        iso8601_format_inner(
+            self.is_negative(),
+            self.whole_seconds().unsigned_abs(),
+            self.subsec_nanoseconds().unsigned_abs(),
+        )
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl HumanFormat for jiff::SignedDuration {
+    fn human_format(&self) -> String {
+        signed_format_inner(
+            self.is_negative(),
+            self.as_secs().unsigned_abs(),
+            self.subsec_nanos().unsigned_abs(),
+        )
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl SystemdFormat for jiff::SignedDuration {
+    fn systemd_format(&self) -> String {
+        systemd_format_inner(self.is_negative(), self.as_nanos().unsigned_abs())
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl GoFormat for jiff::SignedDuration {
+    fn go_format(&self) -> String {
+        go_format_inner(self.is_negative(), self.as_nanos().unsigned_abs())
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl Iso8601Format for jiff::SignedDuration {
+    fn iso8601_format(&self) -> String {
+        iso8601_format_inner(
+            self.is_negative(),
+            self.as_secs().unsigned_abs(),
+            self.subsec_nanos().unsigned_abs(),
+        )
+    }
+}
+
+/// Every unit of `span` with its value, largest first.
+#[cfg(feature = "jiff")]
+fn jiff_span_units(span: &jiff::Span) -> [(i64, TimeUnit); 10] {
+    [
+        (span.get_years().into(), TimeUnit::Year),
+        (span.get_months().into(), TimeUnit::Month),
+        (span.get_weeks().into(), TimeUnit::Week),
+        (span.get_days().into(), TimeUnit::Day),
+        (span.get_hours().into(), TimeUnit::Hour),
+        (span.get_minutes(), TimeUnit::Minute),
+        (span.get_seconds(), TimeUnit::Second),
+        (span.get_milliseconds(), TimeUnit::MilliSecond),
+        (span.get_microseconds(), TimeUnit::MicroSecond),
+        (span.get_nanoseconds(), TimeUnit::NanoSecond),
+    ]
+}
+
+/// The absolute length of `span` with units `unit_nanos` long.
+#[cfg(feature = "jiff")]
+fn jiff_span_abs_nanos(span: &jiff::Span, unit_nanos: fn(TimeUnit) -> u64) -> u128 {
+    jiff_span_units(span)
+        .iter()
+        .map(|(val, unit)| val.unsigned_abs() as u128 * unit_nanos(*unit) as u128)
+        .sum()
+}
+
+/// Spans keep their units as they are: [`human_format`](HumanFormat::human_format) writes each
+/// one, e.g. `1y 2mon 36h`, and [`iso8601_format`](Iso8601Format::iso8601_format) is jiff's own
+/// ISO 8601 format. The Go and systemd formats have no calendar units: Go's counts a year as 365
+/// days and a month as 30 days, systemd's uses systemd's own 365.25 and 30.44 days.
+#[cfg(feature = "jiff")]
+impl HumanFormat for jiff::Span {
+    fn human_format(&self) -> String {
+        let mut format = String::new();
+        for (val, unit) in jiff_span_units(self) {
+            if val != 0 {
+                if !format.is_empty() {
+                    format.push(' ');
+                }
+                format.push_str(&format!("{}{}", val, unit));
+            }
+        }
+        if format.is_empty() {
+            return "0s".to_string();
+        }
+        format
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl SystemdFormat for jiff::Span {
+    fn systemd_format(&self) -> String {
+        systemd_format_inner(self.is_negative(), jiff_span_abs_nanos(self, systemd_nanos))
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl GoFormat for jiff::Span {
+    fn go_format(&self) -> String {
+        go_format_inner(
+            self.is_negative(),
+            jiff_span_abs_nanos(self, |unit| unit.nanos_per_unit()),
+        )
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl Iso8601Format for jiff::Span {
+    fn iso8601_format(&self) -> String {
+        self.to_string()
     }
 }
 
@@ -114,7 +490,183 @@ mod tests {
         assert_eq!(duration.human_format(), "7h");
     }
 
-    #[cfg(all(feature = "serde", feature = "chrono"))]
+    #[test]
+    fn test_iso8601_format() {
+        assert_eq!(Duration::ZERO.iso8601_format(), "PT0S");
+        assert_eq!(Duration::new(5400, 0).iso8601_format(), "PT1H30M");
+        assert_eq!(Duration::new(0, 1).iso8601_format(), "PT0.000000001S");
+        assert_eq!(Duration::from_millis(1500).iso8601_format(), "PT1.5S");
+        assert_eq!(Duration::new(3 * 86400, 0).iso8601_format(), "P3D");
+        assert_eq!(Duration::new(86400 + 1, 0).iso8601_format(), "P1DT1S");
+
+        let duration = parse("1y 2mon 3d 4h 5m 6s 789ms").unwrap();
+        assert_eq!(duration.iso8601_format(), "P1Y2M3DT4H5M6.789S");
+
+        let duration = parse("1y 12d 3s").unwrap();
+        assert_eq!(duration.iso8601_format(), "P1Y12DT3S");
+    }
+
+    /// `durationTests` from Go's `time/time_test.go`.
+    #[test]
+    fn test_go_format() {
+        let cases = [
+            ("0s", 0),
+            ("1ns", 1),
+            ("1.1µs", 1_100),
+            ("2.2ms", 2_200_000),
+            ("3.3s", 3_300_000_000),
+            ("4m5s", (4 * 60 + 5) * 1_000_000_000),
+            ("4m5.001s", (4 * 60 + 5) * 1_000_000_000 + 1_000_000),
+            (
+                "5h6m7.001s",
+                (5 * 3600 + 6 * 60 + 7) * 1_000_000_000 + 1_000_000,
+            ),
+            ("8m0.000000001s", 8 * 60 * 1_000_000_000 + 1),
+            ("2562047h47m16.854775807s", i64::MAX as u64),
+        ];
+        for (expected, nanos) in cases {
+            assert_eq!(Duration::from_nanos(nanos).go_format(), expected);
+            assert_eq!(
+                crate::parse_go(expected),
+                Ok(crate::SignedDuration::from_nanos(nanos as i128))
+            );
+        }
+
+        let duration = crate::parse_go("2h45m0s").unwrap();
+        assert_eq!(Duration::try_from(duration).unwrap().go_format(), "2h45m0s");
+        assert_eq!(Duration::from_secs(3600).go_format(), "1h0m0s");
+        assert_eq!(Duration::from_millis(1).go_format(), "1ms");
+    }
+
+    #[test]
+    fn test_systemd_format() {
+        use crate::{parse_systemd, TimeUnit};
+
+        let cases = [
+            ("0", Duration::ZERO),
+            ("0", Duration::from_nanos(999)),
+            ("1us", Duration::from_micros(1)),
+            ("1.500000s", Duration::from_millis(1500)),
+            ("1.500ms", Duration::from_micros(1500)),
+            ("59.999999s", Duration::new(59, 999_999_999)),
+            ("1min 1.500000s", Duration::new(61, 500_000_000)),
+            ("1h 30min", Duration::new(5400, 0)),
+            ("2w", Duration::new(14 * 86400, 0)),
+            ("1month", Duration::new(2_629_800, 0)),
+            ("1y 1d", Duration::new(31_557_600 + 86400, 0)),
+            ("infinity", Duration::MAX),
+        ];
+        for (expected, duration) in cases {
+            assert_eq!(duration.systemd_format(), expected);
+        }
+
+        for input in [
+            "1h 30min",
+            "5 months",
+            "1y",
+            "1.5s",
+            "3d 4h 5min 6s 7ms 8us",
+        ] {
+            let duration = parse_systemd(input, TimeUnit::Second).unwrap();
+            let formatted = duration.systemd_format();
+            assert_eq!(
+                parse_systemd(&formatted, TimeUnit::Second),
+                Ok(duration),
+                "{input} -> {formatted}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_iso8601_format_round_trip() {
+        for input in [
+            "0s",
+            "1ns",
+            "59s 999ms 999us 999ns",
+            "1y 1w 5d 3s",
+            "751d 1mon 3week 5d 2ns",
+            "364d 23h 59m 59s",
+            "584y",
+        ] {
+            let duration = parse(input).unwrap();
+            let iso = duration.iso8601_format();
+            assert_eq!(crate::parse_iso8601(&iso), Ok(duration), "{input} -> {iso}");
+        }
+
+        for duration in [
+            Duration::from_nanos(u64::MAX),
+            Duration::from_nanos(u64::MAX) + Duration::from_nanos(1),
+            Duration::MAX,
+        ] {
+            let iso = duration.iso8601_format();
+            assert_eq!(crate::parse_iso8601(&iso), Ok(duration), "{iso}");
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_iso8601_format_chrono() {
+        let duration = crate::parse_chrono("1d 2h 3.5s").unwrap();
+        assert_eq!(duration.iso8601_format(), "P1DT2H3.5S");
+        assert_eq!(crate::parse_chrono(duration.iso8601_format()), Ok(duration));
+
+        let duration = crate::parse_chrono("-1d 2h 3.5s").unwrap();
+        assert_eq!(duration.iso8601_format(), "-PT21H59M56.5S");
+        assert_eq!(crate::parse_chrono(duration.iso8601_format()), Ok(duration));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_go_format_chrono() {
+        assert_eq!(
+            CDuration::nanoseconds(i64::MIN).go_format(),
+            "-2562047h47m16.854775808s"
+        );
+        assert_eq!(CDuration::milliseconds(-1500).go_format(), "-1.5s");
+        assert_eq!(CDuration::microseconds(-1).go_format(), "-1µs");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_systemd_format_chrono() {
+        assert_eq!(CDuration::minutes(-90).systemd_format(), "-1h -30min");
+        let duration = CDuration::milliseconds(-90_500);
+        assert_eq!(duration.systemd_format(), "-1min -30.500000s");
+        assert_eq!(crate::parse_chrono(duration.systemd_format()), Ok(duration));
+        assert_eq!(CDuration::milliseconds(1500).systemd_format(), "1.500000s");
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_systemd_format_time() {
+        assert_eq!(TDuration::minutes(-90).systemd_format(), "-1h -30min");
+        assert_eq!(TDuration::ZERO.systemd_format(), "0");
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_go_format_time() {
+        assert_eq!(
+            TDuration::nanoseconds(i64::MIN).go_format(),
+            "-2562047h47m16.854775808s"
+        );
+        assert_eq!(TDuration::minutes(-90).go_format(), "-1h30m0s");
+        assert_eq!(TDuration::ZERO.go_format(), "0s");
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_iso8601_format_time() {
+        let duration = crate::parse_time("1d 2h 3.5s").unwrap();
+        assert_eq!(duration.iso8601_format(), "P1DT2H3.5S");
+        assert_eq!(crate::parse_time(duration.iso8601_format()), Ok(duration));
+
+        let duration = crate::parse_time("-1mon 1ns").unwrap();
+        assert_eq!(duration.iso8601_format(), "-P29DT23H59M59.999999999S");
+        assert_eq!(crate::parse_time(duration.iso8601_format()), Ok(duration));
+    }
+
+    #[cfg(feature = "chrono")]
     #[test]
     fn test_human_format_chrono() {
         let duration = crate::parse_chrono("0s").unwrap();
@@ -134,9 +686,13 @@ mod tests {
 
         let duration = crate::parse_chrono("    7h  0s  ").unwrap();
         assert_eq!(duration.human_format(), "7h");
+
+        let duration = crate::parse_chrono("-1h -30m -2ns").unwrap();
+        assert_eq!(duration.human_format(), "-1h -30min -2ns");
+        assert_eq!(crate::parse_chrono(duration.human_format()), Ok(duration));
     }
 
-    #[cfg(all(feature = "serde", feature = "time"))]
+    #[cfg(feature = "time")]
     #[test]
     fn test_human_format_time() {
         let duration = crate::parse_time("0s").unwrap();
@@ -156,5 +712,54 @@ mod tests {
 
         let duration = crate::parse_time("    7h  0s  ").unwrap();
         assert_eq!(duration.human_format(), "7h");
+
+        let duration = crate::parse_time("-1h -30m -2ns").unwrap();
+        assert_eq!(duration.human_format(), "-1h -30min -2ns");
+        assert_eq!(crate::parse_time(duration.human_format()), Ok(duration));
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_format_jiff() {
+        let span = crate::parse_jiff_span("1y 2mon 36h 1.5s").unwrap();
+        assert_eq!(span.human_format(), "1y 2mon 36h 1s 500ms");
+        assert_eq!(span.iso8601_format(), "P1Y2MT36H1.5S");
+        assert_eq!(
+            crate::parse_jiff_span(span.human_format())
+                .unwrap()
+                .fieldwise(),
+            span
+        );
+        assert_eq!(jiff::Span::new().human_format(), "0s");
+
+        let span = crate::parse_jiff_span("-1w -1h").unwrap();
+        assert_eq!(span.human_format(), "-1w -1h");
+        assert_eq!(span.go_format(), "-169h0m0s");
+        assert_eq!(span.systemd_format(), "-1w -1h");
+
+        for (input, expected, seconds) in [
+            ("1y", "1y", 31_557_600),
+            ("1mon", "1month", 2_629_800),
+            ("1y 1mon 1d", "1y 1month 1d", 31_557_600 + 2_629_800 + 86400),
+        ] {
+            let span = crate::parse_jiff_span(input).unwrap();
+            assert_eq!(span.systemd_format(), expected);
+            assert_eq!(
+                crate::parse_systemd(span.systemd_format(), crate::TimeUnit::Second),
+                Ok(Duration::from_secs(seconds))
+            );
+        }
+
+        let duration = crate::parse_jiff_signed("-1d 2h 3.5s").unwrap();
+        assert_eq!(duration.human_format(), "-21h -59min -56s -500ms");
+        assert_eq!(duration.iso8601_format(), "-PT21H59M56.5S");
+        assert_eq!(
+            jiff::SignedDuration::from_millis(-1500).go_format(),
+            "-1.5s"
+        );
+        assert_eq!(
+            jiff::SignedDuration::from_mins(90).systemd_format(),
+            "1h 30min"
+        );
     }
 }
@@ -0,0 +1,390 @@
+use crate::error::token_len;
+use crate::{DurationError, ErrorKind, SignedDuration};
+use std::ops::Range;
+
+/// Nanoseconds per unit, mirroring `unitMap` in Go's `time` package.
+const GO_UNITS: [(&str, u64); 8] = [
+    ("ns", 1),
+    ("us", 1_000),
+    ("µs", 1_000), // U+00B5 micro sign
+    ("μs", 1_000), // U+03BC Greek small letter mu
+    ("ms", 1_000_000),
+    ("s", 1_000_000_000),
+    ("m", 60 * 1_000_000_000),
+    ("h", 60 * 60 * 1_000_000_000),
+];
+
+const GO_OVERFLOW: u64 = 1 << 63;
+
+/// Quote `s` the way Go's `time` package does in its error messages: printable ASCII is kept,
+/// everything else is written byte by byte as `\xNN`.
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        if c.is_ascii() && c >= ' ' {
+            if c == '"' || c == '\\' {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        } else {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                quoted.push_str(&format!("\\x{:02x}", byte));
+            }
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Consume leading `[0-9]*`, failing on `u64` overflow like Go's `leadingInt`.
+fn leading_int(s: &str) -> Option<(u64, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let mut x: u64 = 0;
+    for c in s[..end].bytes() {
+        if x > GO_OVERFLOW / 10 {
+            return None;
+        }
+        x = x * 10 + u64::from(c - b'0');
+        if x > GO_OVERFLOW {
+            return None;
+        }
+    }
+    Some((x, &s[end..]))
+}
+
+/// Consume leading `[0-9]*` after a decimal point like Go's `leadingFraction`, returning the
+/// digits as an integer with its scale. Digits beyond `u64` precision are dropped.
+fn leading_fraction(s: &str) -> (u64, f64, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (mut x, mut scale, mut overflow): (u64, f64, bool) = (0, 1.0, false);
+    for c in s[..end].bytes() {
+        if overflow {
+            continue;
+        }
+        if x > (GO_OVERFLOW - 1) / 10 {
+            overflow = true;
+            continue;
+        }
+        let y = x * 10 + u64::from(c - b'0');
+        if y > GO_OVERFLOW {
+            overflow = true;
+            continue;
+        }
+        x = y;
+        scale *= 10.0;
+    }
+    (x, scale, &s[end..])
+}
+
+/// parse a Go duration string such as `1h15m30.5s` or `-1.5h`, exactly like Go's `time.ParseDuration`
+///
+/// The grammar is `[-+]?([0-9]*(\.[0-9]*)?[a-z]+)+` with the units `ns`, `us` (or `µs`, `μs`),
+/// `ms`, `s`, `m` and `h`. Every term needs a unit except the lone string `0`, there is no
+/// whitespace, and the result must fit in Go's `int64` nanoseconds. Fractions are computed the
+/// way Go computes them, so the nanosecond value always agrees with Go's. Errors are
+/// [`DurationError`]s whose messages are Go's, e.g. `time: unknown unit "d" in duration "1d"`.
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::{parse_go, ErrorKind, SignedDuration};
+/// use std::time::Duration;
+///
+/// let duration = parse_go("1h15m30.5s").unwrap();
+/// assert_eq!(Duration::try_from(duration), Ok(Duration::new(4530, 500_000_000)));
+///
+/// let duration = parse_go("-1.5h").unwrap();
+/// assert_eq!(duration, SignedDuration::from_nanos(-5400 * 1_000_000_000));
+///
+/// let err = parse_go("1d").unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::InvalidUnit);
+/// assert_eq!(err.token(), "d");
+/// assert_eq!(err.to_string(), r#"time: unknown unit "d" in duration "1d""#);
+/// ```
+pub fn parse_go(input: impl AsRef<str>) -> Result<SignedDuration, DurationError> {
+    let orig = input.as_ref();
+    // every error but the unit ones is Go's "invalid duration", whatever its kind
+    let error = |kind: ErrorKind, span: Range<usize>, message: String| {
+        let expected = match kind {
+            ErrorKind::InvalidUnit | ErrorKind::MissingUnit => {
+                GO_UNITS.iter().map(|(name, _)| name.to_string()).collect()
+            }
+            _ => Vec::new(),
+        };
+        DurationError::new(kind, orig, span, expected, message)
+    };
+    let invalid = |kind: ErrorKind, span: Range<usize>| {
+        error(
+            kind,
+            span,
+            format!("time: invalid duration {}", quote(orig)),
+        )
+    };
+    let offset = |rest: &str| orig.len() - rest.len();
+
+    let mut s = orig;
+    let mut negative = false;
+    if let Some(rest) = s.strip_prefix(['-', '+']) {
+        negative = s.starts_with('-');
+        s = rest;
+    }
+    if s == "0" {
+        return Ok(SignedDuration::ZERO);
+    }
+    if orig.is_empty() {
+        return Err(invalid(ErrorKind::Empty, 0..0));
+    }
+    if s.is_empty() {
+        return Err(invalid(ErrorKind::Syntax, orig.len()..orig.len()));
+    }
+
+    let mut total: u64 = 0;
+    while !s.is_empty() {
+        let start = offset(s);
+        // The next character must be [0-9.]
+        if !s.starts_with(|c: char| c == '.' || c.is_ascii_digit()) {
+            return Err(invalid(ErrorKind::Syntax, start..start + token_len(s)));
+        }
+        let before = s.len();
+        let (mut value, rest) = leading_int(s).ok_or_else(|| {
+            let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            invalid(ErrorKind::Overflow, start..start + digits)
+        })?;
+        s = rest;
+        let pre = before != s.len();
+
+        let (mut fraction, mut scale, mut post) = (0, 1.0, false);
+        if let Some(rest) = s.strip_prefix('.') {
+            (fraction, scale, s) = leading_fraction(rest);
+            post = rest.len() != s.len();
+        }
+        if !pre && !post {
+            return Err(invalid(ErrorKind::Syntax, start..offset(s)));
+        }
+
+        let end = s
+            .find(|c: char| c == '.' || c.is_ascii_digit())
+            .unwrap_or(s.len());
+        if end == 0 {
+            return Err(error(
+                ErrorKind::MissingUnit,
+                offset(s)..offset(s),
+                format!("time: missing unit in duration {}", quote(orig)),
+            ));
+        }
+        let (unit, rest) = s.split_at(end);
+        let unit_span = offset(s)..offset(rest);
+        s = rest;
+        let unit = GO_UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .map(|(_, nanos)| *nanos)
+            .ok_or_else(|| {
+                error(
+                    ErrorKind::InvalidUnit,
+                    unit_span,
+                    format!(
+                        "time: unknown unit {} in duration {}",
+                        quote(unit),
+                        quote(orig)
+                    ),
+                )
+            })?;
+
+        let term = start..offset(s);
+        if value > GO_OVERFLOW / unit {
+            return Err(invalid(ErrorKind::Overflow, term));
+        }
+        value *= unit;
+        if fraction > 0 {
+            // Go goes through float64 here, which is accurate to the nanosecond for every unit.
+            value += (fraction as f64 * (unit as f64 / scale)) as u64;
+            if value > GO_OVERFLOW {
+                return Err(invalid(ErrorKind::Overflow, term));
+            }
+        }
+        total = match total.checked_add(value) {
+            Some(total) if total <= GO_OVERFLOW => total,
+            _ => return Err(invalid(ErrorKind::Overflow, 0..orig.len())),
+        };
+    }
+
+    if negative {
+        return Ok(SignedDuration::from_nanos(-i128::from(total)));
+    }
+    if total > GO_OVERFLOW - 1 {
+        return Err(invalid(ErrorKind::Overflow, 0..orig.len()));
+    }
+    Ok(SignedDuration::from_nanos(i128::from(total)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catch_err;
+
+    const NS: i128 = 1;
+    const US: i128 = 1_000 * NS;
+    const MS: i128 = 1_000 * US;
+    const S: i128 = 1_000 * MS;
+    const M: i128 = 60 * S;
+    const H: i128 = 60 * M;
+
+    /// `parseDurationTests` from Go's `time/time_test.go`.
+    #[test]
+    fn test_parse_go() {
+        let cases: &[(&str, i128)] = &[
+            // simple
+            ("0", 0),
+            ("5s", 5 * S),
+            ("30s", 30 * S),
+            ("1478s", 1478 * S),
+            // sign
+            ("-5s", -5 * S),
+            ("+5s", 5 * S),
+            ("-0", 0),
+            ("+0", 0),
+            // decimal
+            ("5.0s", 5 * S),
+            ("5.6s", 5 * S + 600 * MS),
+            ("5.s", 5 * S),
+            (".5s", 500 * MS),
+            ("1.0s", S),
+            ("1.00s", S),
+            ("1.004s", S + 4 * MS),
+            ("1.0040s", S + 4 * MS),
+            ("100.00100s", 100 * S + MS),
+            // different units
+            ("10ns", 10 * NS),
+            ("11us", 11 * US),
+            ("12µs", 12 * US),
+            ("12μs", 12 * US),
+            ("13ms", 13 * MS),
+            ("14s", 14 * S),
+            ("15m", 15 * M),
+            ("16h", 16 * H),
+            // composite durations
+            ("3h30m", 3 * H + 30 * M),
+            ("10.5s4m", 4 * M + 10 * S + 500 * MS),
+            ("-2m3.4s", -(2 * M + 3 * S + 400 * MS)),
+            (
+                "1h2m3s4ms5us6ns",
+                H + 2 * M + 3 * S + 4 * MS + 5 * US + 6 * NS,
+            ),
+            ("39h9m14.425s", 39 * H + 9 * M + 14 * S + 425 * MS),
+            // large value
+            ("52763797000ns", 52763797000 * NS),
+            // more than 9 digits after decimal point
+            ("0.3333333333333333333h", 20 * M),
+            // 1<<53+1 cannot be stored precisely in a float64
+            ("9007199254740993ns", (1 << 53) + 1),
+            // largest duration that can be represented by int64 in nanoseconds
+            ("9223372036854775807ns", i64::MAX as i128),
+            ("9223372036854775.807us", i64::MAX as i128),
+            ("9223372036s854ms775us807ns", i64::MAX as i128),
+            ("-9223372036854775808ns", i64::MIN as i128),
+            ("-9223372036854775.808us", i64::MIN as i128),
+            ("-9223372036s854ms775us808ns", i64::MIN as i128),
+            // huge string
+            ("0.100000000000000000000h", 6 * M),
+            // first overflow check in leadingFraction
+            ("0.830103483285477580700h", 49 * M + 48 * S + 372539827 * NS),
+            // strings from Go configs
+            ("1h15m30.5s", H + 15 * M + 30 * S + 500 * MS),
+            ("300ms", 300 * MS),
+            ("-1.5h", -(H + 30 * M)),
+            ("2h45m0s", 2 * H + 45 * M),
+        ];
+        for &(input, nanos) in cases {
+            assert_eq!(
+                parse_go(input),
+                Ok(SignedDuration::from_nanos(nanos)),
+                "{input}"
+            );
+        }
+    }
+
+    /// `parseDurationErrorTests` from Go's `time/time_test.go`, minus the invalid UTF-8 inputs
+    /// that cannot be expressed as `&str`.
+    #[test]
+    fn test_parse_go_err() {
+        let cases: &[(&str, &str)] = &[
+            // invalid
+            ("", r#""""#),
+            ("3", r#""3""#),
+            ("-", r#""-""#),
+            ("s", r#""s""#),
+            (".", r#"".""#),
+            ("-.", r#""-.""#),
+            (".s", r#"".s""#),
+            ("+.s", r#""+.s""#),
+            ("1d", r#""1d""#),
+            ("\u{FFFD}", r#""\xef\xbf\xbd""#),
+            (
+                "\u{FFFD} hello \u{FFFD} world",
+                r#""\xef\xbf\xbd hello \xef\xbf\xbd world""#,
+            ),
+            // overflow
+            ("9223372036854775810ns", r#""9223372036854775810ns""#),
+            ("9223372036854775808ns", r#""9223372036854775808ns""#),
+            // largest negative value of type int64 in nanoseconds should fail
+            ("-9223372036854775809ns", r#""-9223372036854775809ns""#),
+            (
+                "9223372036854775808ns9223372036854775808ns",
+                r#""9223372036854775808ns9223372036854775808ns""#,
+            ),
+        ];
+        for &(input, expected) in cases {
+            let err = parse_go(input).unwrap_err().to_string();
+            assert!(err.contains(expected), "{input}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_parse_go_err_message() {
+        assert_eq!(
+            catch_err!(parse_go("1h 30m")),
+            r#"time: unknown unit "h " in duration "1h 30m""#
+        );
+        assert_eq!(
+            catch_err!(parse_go("3")),
+            r#"time: missing unit in duration "3""#
+        );
+        assert_eq!(catch_err!(parse_go("")), r#"time: invalid duration """#);
+        assert_eq!(
+            catch_err!(parse_go("1d")),
+            r#"time: unknown unit "d" in duration "1d""#
+        );
+    }
+
+    #[test]
+    fn test_parse_go_err_kind() {
+        let cases = [
+            ("", ErrorKind::Empty, 0..0),
+            ("-", ErrorKind::Syntax, 1..1),
+            ("1hx", ErrorKind::InvalidUnit, 1..3),
+            ("1h30", ErrorKind::MissingUnit, 4..4),
+            ("1h.s", ErrorKind::Syntax, 2..3),
+            ("1h s", ErrorKind::InvalidUnit, 1..4),
+            ("99999999999999999999ns", ErrorKind::Overflow, 0..20),
+            ("9223372036854775808ns", ErrorKind::Overflow, 0..21),
+            (
+                "9223372036854775808ns9223372036854775808ns",
+                ErrorKind::Overflow,
+                0..42,
+            ),
+        ];
+        for (input, kind, span) in cases {
+            let err = parse_go(input).unwrap_err();
+            assert_eq!(err.kind(), kind, "{input}");
+            assert_eq!(err.span(), span, "{input}");
+        }
+        let err = parse_go("1d").unwrap_err();
+        assert_eq!(
+            err.expected(),
+            ["ns", "us", "µs", "μs", "ms", "s", "m", "h"]
+        );
+    }
+}
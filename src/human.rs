@@ -0,0 +1,311 @@
+use crate::{DurationError, HumanFormat};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Internal macro to define a duration newtype that parses and prints human readable strings
+macro_rules! human_duration {
+    ($(#[$doc:meta])* $name:ident($duration_type:ty), $parse:path, $scalar:ty) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub $duration_type);
+
+        impl $name {
+            /// The wrapped duration.
+            pub const fn into_inner(self) -> $duration_type {
+                self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = DurationError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $parse(s).map($name)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0.human_format())
+            }
+        }
+
+        impl Deref for $name {
+            type Target = $duration_type;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl From<$duration_type> for $name {
+            fn from(duration: $duration_type) -> Self {
+                $name(duration)
+            }
+        }
+
+        impl From<$name> for $duration_type {
+            fn from(duration: $name) -> Self {
+                duration.0
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, rhs: $name) -> $name {
+                $name(self.0 + rhs.0)
+            }
+        }
+
+        impl Add<$duration_type> for $name {
+            type Output = $name;
+
+            fn add(self, rhs: $duration_type) -> $name {
+                $name(self.0 + rhs)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: $name) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, rhs: $name) -> $name {
+                $name(self.0 - rhs.0)
+            }
+        }
+
+        impl Sub<$duration_type> for $name {
+            type Output = $name;
+
+            fn sub(self, rhs: $duration_type) -> $name {
+                $name(self.0 - rhs)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: $name) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Mul<$scalar> for $name {
+            type Output = $name;
+
+            fn mul(self, rhs: $scalar) -> $name {
+                $name(self.0 * rhs)
+            }
+        }
+
+        impl MulAssign<$scalar> for $name {
+            fn mul_assign(&mut self, rhs: $scalar) {
+                self.0 = self.0 * rhs;
+            }
+        }
+
+        impl Div<$scalar> for $name {
+            type Output = $name;
+
+            fn div(self, rhs: $scalar) -> $name {
+                $name(self.0 / rhs)
+            }
+        }
+
+        impl DivAssign<$scalar> for $name {
+            fn div_assign(&mut self, rhs: $scalar) {
+                self.0 = self.0 / rhs;
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                crate::serialize_duration(&self.0, serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                crate::deserialize_duration(deserializer).map($name)
+            }
+        }
+    };
+}
+
+human_duration!(
+    /// A `std::time::Duration` that parses with [`parse`](crate::parse) and prints with
+    /// [`HumanFormat::human_format`], so it can be used directly as a struct field, a `clap`
+    /// argument or a map value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use duration_str::HumanDuration;
+    /// use std::time::Duration;
+    ///
+    /// let timeout: HumanDuration = "1m 30s".parse().unwrap();
+    /// assert_eq!(*timeout, Duration::new(90, 0));
+    /// assert_eq!(timeout.to_string(), "1min 30s");
+    /// assert_eq!((timeout * 2).as_secs(), 180);
+    /// ```
+    HumanDuration(Duration),
+    crate::parse,
+    u32
+);
+
+#[cfg(feature = "chrono")]
+human_duration!(
+    /// A `chrono::Duration` that parses with [`parse_chrono`](crate::parse_chrono) and prints
+    /// with [`HumanFormat::human_format`]; see [`HumanDuration`].
+    HumanChronoDuration(chrono::Duration),
+    crate::parse_chrono,
+    i32
+);
+
+#[cfg(feature = "time")]
+human_duration!(
+    /// A `time::Duration` that parses with [`parse_time`](crate::parse_time) and prints with
+    /// [`HumanFormat::human_format`]; see [`HumanDuration`].
+    HumanTimeDuration(time::Duration),
+    crate::parse_time,
+    i32
+);
+
+#[cfg(feature = "chrono")]
+impl std::ops::Neg for HumanChronoDuration {
+    type Output = HumanChronoDuration;
+
+    fn neg(self) -> HumanChronoDuration {
+        HumanChronoDuration(-self.0)
+    }
+}
+
+#[cfg(feature = "time")]
+impl std::ops::Neg for HumanTimeDuration {
+    type Output = HumanTimeDuration;
+
+    fn neg(self) -> HumanTimeDuration {
+        HumanTimeDuration(-self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human_duration() {
+        let duration: HumanDuration = "1h 30m".parse().unwrap();
+        assert_eq!(duration, HumanDuration(Duration::new(5400, 0)));
+        assert_eq!(duration.to_string(), "1h 30min");
+        assert_eq!(duration.as_secs(), 5400);
+        assert_eq!(Duration::from(duration), Duration::new(5400, 0));
+        assert_eq!(duration.into_inner(), Duration::new(5400, 0));
+        assert_eq!(
+            HumanDuration::from(Duration::from_millis(1500)).to_string(),
+            "1s 500ms"
+        );
+        assert!("1x".parse::<HumanDuration>().is_err());
+
+        let mut duration = duration;
+        *duration += Duration::new(1, 0);
+        assert_eq!(duration, HumanDuration(Duration::new(5401, 0)));
+    }
+
+    #[test]
+    fn test_human_duration_ops() {
+        let a = HumanDuration(Duration::new(60, 0));
+        let b = HumanDuration(Duration::new(30, 0));
+        assert_eq!(a + b, HumanDuration(Duration::new(90, 0)));
+        assert_eq!(a - b, HumanDuration(Duration::new(30, 0)));
+        assert_eq!(a + Duration::new(1, 0), HumanDuration(Duration::new(61, 0)));
+        assert_eq!(a - Duration::new(1, 0), HumanDuration(Duration::new(59, 0)));
+        assert_eq!(a * 3, HumanDuration(Duration::new(180, 0)));
+        assert_eq!(a / 4, HumanDuration(Duration::new(15, 0)));
+
+        let mut c = a;
+        c += b;
+        c -= HumanDuration(Duration::new(10, 0));
+        c *= 2;
+        c /= 8;
+        assert_eq!(c, HumanDuration(Duration::new(20, 0)));
+        assert!(b < a);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_human_chrono_duration() {
+        let duration: HumanChronoDuration = "-1h -30m".parse().unwrap();
+        assert_eq!(*duration, chrono::Duration::minutes(-90));
+        assert_eq!(duration.to_string(), "-1h -30min");
+        assert_eq!(
+            -duration * 2,
+            HumanChronoDuration(chrono::Duration::hours(3))
+        );
+        assert_eq!(chrono::Duration::from(duration / 3).num_minutes(), -30);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_human_time_duration() {
+        let duration: HumanTimeDuration = "2d".parse().unwrap();
+        assert_eq!(*duration, time::Duration::days(2));
+        assert_eq!(duration.to_string(), "2d");
+        assert_eq!(-duration / 2, HumanTimeDuration(time::Duration::days(-1)));
+        assert_eq!(duration.whole_hours(), 48);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_human_duration_serde() {
+        use std::collections::HashMap;
+
+        #[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+        struct Config {
+            timeout: HumanDuration,
+            retry: Option<HumanDuration>,
+            backoff: Vec<HumanDuration>,
+            endpoints: HashMap<String, HumanDuration>,
+        }
+
+        let json = r#"{"timeout":"1m30s","retry":null,"backoff":["1s",2],"endpoints":{"a":"5m"}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(*config.timeout, Duration::new(90, 0));
+        assert_eq!(config.retry, None);
+        assert_eq!(
+            config.backoff,
+            [
+                HumanDuration(Duration::new(1, 0)),
+                HumanDuration(Duration::new(2, 0))
+            ]
+        );
+        assert_eq!(*config.endpoints["a"], Duration::new(300, 0));
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            r#"{"timeout":"1min 30s","retry":null,"backoff":["1s","2s"],"endpoints":{"a":"5min"}}"#
+        );
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::span::SpanValue;
+use crate::unit::{quantity_nanos, TimeUnit};
+use crate::{round_nanos, DError, DurationError, DurationParser, ExpectErr, ONE_SECOND_NANOSECOND};
+use rust_decimal::Decimal;
+use std::time::Duration;
+use winnow::ascii::{digit1, multispace0};
+use winnow::combinator::{cut_err, eof, fail, opt, peek, preceded, terminated};
+use winnow::error::{ContextError, ErrMode, FromExternalError, StrContext, StrContextValue};
+use winnow::token::one_of;
+use winnow::ModalResult as WResult;
+use winnow::Parser;
+
+pub(crate) const EXPECT_DESIGNATOR: &str = "ISO 8601 duration, e.g. P3DT4H30M";
+
+/// Whether `input` looks like an ISO 8601 duration, i.e. starts with `P` after an optional sign.
+pub(crate) fn is_iso8601(input: &str) -> bool {
+    let input = input.trim_start();
+    let input = input.strip_prefix(['+', '-']).unwrap_or(input);
+    input.starts_with(['P', 'p'])
+}
+
+/// A component value such as `3`, `1.5` or `1,5`.
+fn iso_number<'a>(input: &mut &'a str) -> WResult<&'a str> {
+    (digit1, opt((one_of(['.', ',']), digit1)))
+        .take()
+        .parse_next(input)
+}
+
+/// The designator `upper`, or its lowercase form unless `parser` is case sensitive.
+fn designator<'a>(
+    upper: char,
+    parser: &DurationParser,
+) -> impl Parser<&'a str, char, ErrMode<ContextError>> {
+    let lowercase = !parser.case_sensitive;
+    one_of(move |c: char| c == upper || (lowercase && c == upper.to_ascii_lowercase()))
+}
+
+/// An optional `<number><designator>` pair, as the quantity and its unit. A unit that `parser`
+/// does not allow is an error at its designator.
+fn component(
+    input: &mut &str,
+    upper: char,
+    unit: TimeUnit,
+    parser: &DurationParser,
+) -> WResult<Option<(Decimal, TimeUnit)>> {
+    let Some(value) =
+        opt(terminated(iso_number, peek(designator(upper, parser)))).parse_next(input)?
+    else {
+        return Ok(None);
+    };
+    cut_err(designator(upper, parser).verify(|_| parser.allows(unit)))
+        .context(StrContext::Expected(StrContextValue::Description(
+            TimeUnit::get_expect_val(),
+        )))
+        .parse_next(input)?;
+    let quantity = quantity_nanos(value.replace(',', "."), Decimal::ONE)
+        .map_err(|err| ErrMode::from_external_error(input, err).cut())?;
+    Ok(Some((quantity, unit)))
+}
+
+type Component = Option<(Decimal, TimeUnit)>;
+
+fn date_part(input: &mut &str, parser: &DurationParser) -> WResult<[Component; 4]> {
+    Ok([
+        component(input, 'Y', TimeUnit::Year, parser)?,
+        component(input, 'M', TimeUnit::Month, parser)?,
+        component(input, 'W', TimeUnit::Week, parser)?,
+        component(input, 'D', TimeUnit::Day, parser)?,
+    ])
+}
+
+fn time_part(input: &mut &str, parser: &DurationParser) -> WResult<[Component; 3]> {
+    Ok([
+        component(input, 'H', TimeUnit::Hour, parser)?,
+        component(input, 'M', TimeUnit::Minute, parser)?,
+        component(input, 'S', TimeUnit::Second, parser)?,
+    ])
+}
+
+/// `[+-]P[nY][nM][nW][nD][T[nH][nM][nS]]`, returning the sign and every given component.
+pub(crate) fn iso8601(
+    input: &mut &str,
+    parser: &DurationParser,
+) -> WResult<(bool, Vec<(Decimal, TimeUnit)>)> {
+    let expected = || StrContext::Expected(StrContextValue::Description(EXPECT_DESIGNATOR));
+
+    multispace0.parse_next(input)?;
+    let negative = opt(one_of(['+', '-'])).parse_next(input)? == Some('-');
+    designator('P', parser)
+        .context(expected())
+        .parse_next(input)?;
+    let date = date_part(input, parser)?;
+    let time = opt(preceded(
+        designator('T', parser),
+        cut_err(
+            (|input: &mut &str| time_part(input, parser))
+                .verify(|time: &[Component; 3]| time.iter().any(Option::is_some)),
+        )
+        .context(expected()),
+    ))
+    .parse_next(input)?
+    .unwrap_or_default();
+    cut_err((multispace0, eof))
+        .context(expected())
+        .parse_next(input)?;
+
+    let components: Vec<(Decimal, TimeUnit)> = date.into_iter().chain(time).flatten().collect();
+    if components.is_empty() {
+        return cut_err(fail).context(expected()).parse_next(input);
+    }
+    Ok((negative, components))
+}
+
+/// Parse an ISO 8601 `PnYnMnWnDTnHnMnS` duration to signed nanoseconds, with the unit lengths,
+/// allowed units and case sensitivity of `parser`.
+pub(crate) fn parse_iso8601_nanos(
+    input: &str,
+    parser: &DurationParser,
+) -> Result<i128, DurationError> {
+    let (negative, components) = (|input: &mut &str| iso8601(input, parser))
+        .parse(input)
+        .map_err(|err| DurationError::from_parse_error(err).suggest_unit(parser))?;
+    let nanos = components
+        .into_iter()
+        .try_fold(Decimal::ZERO, |acc, (quantity, unit)| {
+            quantity
+                .checked_mul(parser.unit_nanos(unit))
+                .and_then(|nanos| acc.checked_add(nanos))
+        })
+        .ok_or(DError::OverflowError)
+        .and_then(round_nanos)
+        .map_err(|err| DurationError::from_error(input, err))?;
+    Ok(if negative { -nanos } else { nanos })
+}
+
+/// Parse an ISO 8601 duration like [`parse_iso8601_nanos`], but keep calendar units apart.
+pub(crate) fn parse_iso8601_span(
+    input: &str,
+    parser: &DurationParser,
+) -> Result<SpanValue, DurationError> {
+    let (negative, components) = (|input: &mut &str| iso8601(input, parser))
+        .parse(input)
+        .map_err(|err| DurationError::from_parse_error(err).suggest_unit(parser))?;
+    let value = components
+        .into_iter()
+        .try_fold(SpanValue::default(), |acc, (quantity, unit)| {
+            acc.checked_add(&SpanValue::from_quantity(unit, quantity, parser)?)
+        })
+        .map_err(|err| DurationError::from_error(input, err))?;
+    Ok(if negative { -value } else { value })
+}
+
+/// convert an ISO 8601 duration such as `PT1H30M` or `P3DT4H` to `std::time::Duration`
+///
+/// Years and months use the same fixed lengths as the `y` and `mon` units: 365 and 30 days.
+/// Every component may carry a fraction (`PT0.5S`, `P1,5D`), and a leading `-` negates the
+/// whole duration, which `std::time::Duration` rejects.
+///
+/// [`parse`](crate::parse) recognises ISO 8601 input on its own, so this function is only
+/// needed to refuse the crate's other syntaxes. Designators may be written in either case here;
+/// [`DurationParser`] applies its own case sensitivity, allowed units and unit lengths.
+/// Unlike [`parse`](crate::parse), the result may be longer than `u64::MAX` nanoseconds, up to
+/// `Duration::MAX`, so everything [`Iso8601Format::iso8601_format`] writes parses back.
+///
+/// [`Iso8601Format::iso8601_format`]: crate::Iso8601Format::iso8601_format
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::parse_iso8601;
+/// use std::time::Duration;
+///
+/// let duration = parse_iso8601("PT1H30M").unwrap();
+/// assert_eq!(duration, Duration::new(5400, 0));
+///
+/// let duration = parse_iso8601("P3DT4H").unwrap();
+/// assert_eq!(duration, Duration::new(3 * 24 * 3600 + 4 * 3600, 0));
+///
+/// let duration = parse_iso8601("P1W").unwrap();
+/// assert_eq!(duration, Duration::new(7 * 24 * 3600, 0));
+///
+/// let duration = parse_iso8601("PT0.250S").unwrap();
+/// assert_eq!(duration, Duration::from_millis(250));
+///
+/// assert!(parse_iso8601("1h30m").is_err());
+/// ```
+pub fn parse_iso8601(input: impl AsRef<str>) -> Result<Duration, DurationError> {
+    let input = input.as_ref();
+    let nanos = parse_iso8601_nanos(input, &DurationParser::new().case_sensitive(false))?;
+    if nanos < 0 {
+        return Err(DurationError::from_error(input, DError::NegativeError));
+    }
+    // up to `Duration::MAX` rather than `u64` nanoseconds, so every `iso8601_format` output
+    // parses back
+    let second = i128::from(ONE_SECOND_NANOSECOND);
+    let secs = u64::try_from(nanos / second)
+        .map_err(|_| DurationError::from_error(input, DError::OverflowError))?;
+    Ok(Duration::new(secs, (nanos % second) as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{catch_err, parse, parse_signed, SignedDuration};
+
+    #[test]
+    fn test_is_iso8601() {
+        assert!(is_iso8601("PT1H"));
+        assert!(is_iso8601("  -P1D"));
+        assert!(is_iso8601("+pt1h"));
+        assert!(!is_iso8601("1h"));
+        assert!(!is_iso8601("-1h"));
+        assert!(!is_iso8601(""));
+    }
+
+    #[test]
+    fn test_parse_iso8601() {
+        assert_eq!(parse_iso8601("PT0S"), Ok(Duration::ZERO));
+        assert_eq!(parse_iso8601("PT36H"), Ok(Duration::new(36 * 3600, 0)));
+        assert_eq!(parse_iso8601("PT1H30M"), Ok(Duration::new(5400, 0)));
+        assert_eq!(parse_iso8601("PT90M"), Ok(Duration::new(5400, 0)));
+        assert_eq!(parse_iso8601("P1M"), Ok(Duration::new(30 * 86400, 0)));
+        assert_eq!(parse_iso8601("P1Y"), Ok(Duration::new(365 * 86400, 0)));
+        assert_eq!(parse_iso8601("P2W"), Ok(Duration::new(14 * 86400, 0)));
+        assert_eq!(
+            parse_iso8601("P1Y2M3DT4H5M6.789S"),
+            Ok(Duration::new(
+                365 * 86400 + 2 * 30 * 86400 + 3 * 86400 + 4 * 3600 + 5 * 60 + 6,
+                789_000_000
+            ))
+        );
+        assert_eq!(parse_iso8601("P1,5D"), Ok(Duration::new(36 * 3600, 0)));
+        assert_eq!(parse_iso8601("PT0.000000001S"), Ok(Duration::new(0, 1)));
+        assert_eq!(parse_iso8601(" pt1h30m "), Ok(Duration::new(5400, 0)));
+        assert_eq!(parse_iso8601("+PT1M"), Ok(Duration::new(60, 0)));
+    }
+
+    #[test]
+    fn test_parse_iso8601_negative() {
+        assert_eq!(
+            parse_signed("-PT1H30M"),
+            Ok(SignedDuration::from_nanos(-5400 * 1_000_000_000))
+        );
+        assert_eq!(
+            catch_err!(parse_iso8601("-PT1H")),
+            "negative duration is not supported by std::time::Duration"
+        );
+    }
+
+    #[test]
+    fn test_parse_iso8601_err() {
+        assert_eq!(
+            catch_err!(parse_iso8601("P")),
+            r#"
+P
+ ^
+expected ISO 8601 duration, e.g. P3DT4H30M"#
+                .trim()
+        );
+        assert_eq!(
+            catch_err!(parse_iso8601("PT")),
+            r#"
+PT
+  ^
+expected ISO 8601 duration, e.g. P3DT4H30M"#
+                .trim()
+        );
+        assert_eq!(
+            catch_err!(parse_iso8601("P1H")),
+            r#"
+P1H
+ ^
+expected ISO 8601 duration, e.g. P3DT4H30M"#
+                .trim()
+        );
+        assert_eq!(
+            catch_err!(parse_iso8601("PT1S1M")),
+            r#"
+PT1S1M
+    ^
+expected ISO 8601 duration, e.g. P3DT4H30M"#
+                .trim()
+        );
+        assert_eq!(
+            catch_err!(parse_iso8601("1h")),
+            r#"
+1h
+^
+expected ISO 8601 duration, e.g. P3DT4H30M"#
+                .trim()
+        );
+        assert!(parse_iso8601("P1.D").is_err());
+        assert!(parse_iso8601("P1D T1H").is_err());
+    }
+
+    #[test]
+    fn test_parse_detects_iso8601() {
+        assert_eq!(parse("PT1H30M"), Ok(Duration::new(5400, 0)));
+        assert_eq!(parse("  P1DT12H"), Ok(Duration::new(36 * 3600, 0)));
+    }
+}
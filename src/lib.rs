@@ -25,7 +25,39 @@
 //!
 //! - ns:Nanosecond.Support string value: ["ns" | "NS" | "Nanosecond" | "NanoSecond" | "NANOSECOND" | "nanosecond" | "nSEC"]. e.g. 1ns
 //!
-//! Also, `duration_str` support time duration simple evaluation(+,*). See examples below.
+//! ISO 8601 durations such as `PT1H30M` or `P3DT4H` are recognised by their leading `P`,
+//! see [`parse_iso8601`].
+//!
+//! The `lowercase`, `no_calc` and `cn_unit` features choose the defaults of the free functions;
+//! use a [`DurationParser`] to set case sensitivity, arithmetic, Chinese units, the default unit,
+//! the allowed units and the month/year lengths at runtime.
+//!
+//! Go durations such as `1h15m30.5s` can be parsed with Go's exact grammar and rounding, see
+//! [`parse_go`].
+//!
+//! systemd time spans such as `1h 30min` or `5 months` can be parsed with systemd's units and
+//! month/year lengths, see [`parse_systemd`].
+//!
+//! Clock notation `hh:mm:ss[.fff]` and `mm:ss[.fff]` is accepted too, optionally with a day
+//! prefix written as a unit (`2d 03:00:00`) or .NET style (`2.03:00:00`).
+//!
+//! Quantities may be fractional, e.g. `1.5h` or `0.25d`. The value is computed exactly and then
+//! rounded to the nearest nanosecond, with midpoints rounded away from zero.
+//!
+//! Also, `duration_str` support time duration evaluation(+,-,*,/) with the usual operator
+//! precedence and parentheses, e.g. `(1h + 15m) * 3`. See examples below.
+//! `/` divides a duration by a scalar (`1d / 24` is one hour); use [`parse_ratio`] to divide
+//! one duration by another.
+//!
+//! A leading minus sign makes a duration negative. Negative results are rejected by [`parse`],
+//! use [`parse_signed`], `parse_chrono` or `parse_time` to get a signed duration.
+//!
+//! [`parse_span`] keeps years, months, weeks and days as calendar units, which
+//! [`Span::apply_to`] adds to a date with month ends clamped, e.g. one month after January 31st
+//! is the end of February.
+//!
+//! With the `jiff` feature, `parse_jiff_span` keeps years, months, weeks and days as calendar
+//! units of a `jiff::Span`, and `parse_jiff_signed` returns a `jiff::SignedDuration`.
 //!
 //! # Example
 //! ```rust
@@ -35,6 +67,9 @@
 //! let duration = parse("1d").unwrap();
 //! assert_eq!(duration, Duration::new(24 * 60 * 60, 0));
 //!
+//! let duration = parse("01:30:00").unwrap();
+//! assert_eq!(duration, Duration::new(90 * 60, 0));
+//!
 //! let duration = parse("3m+31").unwrap(); //the default duration unit is second.
 //! assert_eq!(duration, Duration::new(211, 0));
 //!
@@ -75,6 +110,15 @@
 //! assert_eq!(duration, Duration::new(3600, 0));
 //! let duration = parse("42µs").unwrap();
 //! assert_eq!(duration,Duration::from_micros(42));
+//!
+//! let duration = parse("1.5h").unwrap();
+//! assert_eq!(duration, Duration::new(5400, 0));
+//!
+//! let duration = parse("2h + 30m * 2").unwrap();
+//! assert_eq!(duration, Duration::new(3 * 3600, 0));
+//!
+//! let duration = parse("(1h + 15m) * 3").unwrap();
+//! assert_eq!(duration, Duration::new(3 * 3600 + 45 * 60, 0));
 //! ```
 //!
 //! # deserialize to std::time::Duration
@@ -209,30 +253,61 @@
 //! }
 //! ```
 
+mod builder;
+#[cfg(feature = "clap")]
+mod clap;
+pub mod env;
 mod error;
+mod expr;
 pub(crate) mod ext;
+mod go;
+mod human;
+mod iso8601;
 pub(crate) mod macros;
 mod parser;
+#[cfg(feature = "schemars")]
+mod schema;
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(feature = "serde_with")]
+mod serde_with;
+mod signed;
+mod span;
+mod systemd;
 mod unit;
 
+#[cfg(feature = "clap")]
+pub use crate::clap::DurationValueParser;
+#[cfg(feature = "serde_with")]
+pub use crate::serde_with::DurationStr;
+pub use builder::DurationParser;
+pub use go::parse_go;
+#[cfg(feature = "chrono")]
+pub use human::HumanChronoDuration;
+pub use human::HumanDuration;
+#[cfg(feature = "time")]
+pub use human::HumanTimeDuration;
+pub use iso8601::parse_iso8601;
 pub use parser::parse;
 #[cfg(feature = "serde")]
 pub use serde::*;
+pub use signed::SignedDuration;
+pub use span::{Span, SpanAnchor};
 use std::fmt::{Debug, Display};
+pub use systemd::parse_systemd;
+pub use unit::TimeUnit;
 
 use rust_decimal::prelude::ToPrimitive;
-use rust_decimal::Decimal;
+use rust_decimal::RoundingStrategy;
 use std::str::FromStr;
 use std::time::Duration;
 
-pub use crate::error::DError;
-use crate::unit::TimeUnit;
+pub use crate::error::{DError, DurationError, ErrorKind};
 #[cfg(feature = "chrono")]
 pub use naive_date::{
     after_naive_date, after_naive_date_time, before_naive_date, before_naive_date_time,
 };
+pub use rust_decimal::Decimal;
 
 pub use ext::*;
 
@@ -248,13 +323,18 @@ const ONE_WEEK_NANOSECOND: u64 = 7 * ONE_DAY_NANOSECOND;
 const ONE_MONTH_NANOSECOND: u64 = 30 * ONE_DAY_NANOSECOND;
 const ONE_YEAR_NANOSECOND: u64 = 365 * ONE_DAY_NANOSECOND;
 
-// const ONE_SECOND_DECIMAL: Decimal = 1_000_000_000.into();
-fn one_second_decimal() -> Decimal {
-    1_000_000_000.into()
+/// Round an exact nanosecond amount to the nearest nanosecond, midpoints away from zero.
+fn round_nanos(nanos: Decimal) -> DResult<i128> {
+    nanos
+        .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+        .to_i128()
+        .ok_or(DError::OverflowError)
 }
 
 const PLUS: &str = "+";
+const MINUS: &str = "-";
 const STAR: &str = "*";
+const SLASH: &str = "/";
 
 trait ExpectErr {
     type Output: Debug;
@@ -314,7 +394,9 @@ macro_rules! impl_expect_err_internal {
 #[derive(Debug, Eq, PartialEq, Clone)]
 enum CondUnit {
     Plus,
+    Minus,
     Star,
+    Slash,
 }
 
 impl FromStr for CondUnit {
@@ -323,81 +405,46 @@ impl FromStr for CondUnit {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "+" => Ok(CondUnit::Plus),
+            "-" => Ok(CondUnit::Minus),
             "*" => Ok(CondUnit::Star),
+            "/" => Ok(CondUnit::Slash),
             _ => Err(Self::expect_err(s)),
         }
     }
 }
 
-impl_expect_err!(CondUnit, [char; 2], ['+', '*']);
+impl_expect_err!(CondUnit, [char; 4], ['+', '-', '*', '/']);
 
 impl CondUnit {
-    fn init() -> (Self, u64) {
-        (CondUnit::Star, ONE_SECOND_NANOSECOND)
-    }
-
     fn contain(c: char) -> bool {
         Self::expect_val().contains(&c)
     }
 
-    fn change_duration(&self) -> u64 {
-        match self {
-            CondUnit::Plus => 0,
-            CondUnit::Star => ONE_SECOND_NANOSECOND,
-        }
-    }
-
-    fn calc(&self, x: u64, y: u64) -> DResult<Duration> {
+    /// Apply the operator to two exact nanosecond amounts.
+    ///
+    /// Multiplication and division treat both operands as a count of `scalar` nanoseconds,
+    /// the default unit, so with seconds `1m * 10` is ten minutes and `1d / 24` is one hour.
+    fn calc(&self, x: Decimal, y: Decimal, scalar: Decimal) -> DResult<Decimal> {
         let nano_second = match self {
             CondUnit::Plus => x.checked_add(y).ok_or(DError::OverflowError)?,
-            CondUnit::Star => {
-                let x: Decimal = x.into();
-                let y: Decimal = y.into();
-                let ret = (x / one_second_decimal())
-                    .checked_mul(y / one_second_decimal())
+            CondUnit::Minus => x.checked_sub(y).ok_or(DError::OverflowError)?,
+            CondUnit::Star => (x / scalar)
+                .checked_mul(y / scalar)
+                .ok_or(DError::OverflowError)?
+                .checked_mul(scalar)
+                .ok_or(DError::OverflowError)?,
+            CondUnit::Slash => {
+                if y.is_zero() {
+                    return Err(DError::DivisionByZero);
+                }
+                (x / scalar)
+                    .checked_div(y / scalar)
+                    .ok_or(DError::OverflowError)?
+                    .checked_mul(scalar)
                     .ok_or(DError::OverflowError)?
-                    .checked_mul(one_second_decimal())
-                    .ok_or(DError::OverflowError)?;
-                ret.to_u64().ok_or(DError::OverflowError)?
             }
         };
-        Ok(Duration::from_nanos(nano_second))
-    }
-}
-
-trait Calc<T> {
-    fn calc(&self) -> DResult<T>;
-}
-
-impl Calc<(CondUnit, u64)> for Vec<(&str, CondUnit, TimeUnit)> {
-    fn calc(&self) -> DResult<(CondUnit, u64)> {
-        let (mut init_cond, mut init_duration) = CondUnit::init();
-        for (index, (val, cond, time_unit)) in self.iter().enumerate() {
-            if index == 0 {
-                init_cond = cond.clone();
-                init_duration = init_cond.change_duration();
-            } else if &init_cond != cond {
-                return Err(DError::ParseError(format!(
-                    "not support '{}' with '{}' calculate",
-                    init_cond, cond
-                )));
-            }
-            match init_cond {
-                CondUnit::Plus => {
-                    init_duration = init_duration
-                        .checked_add(time_unit.duration(val)?)
-                        .ok_or(DError::OverflowError)?;
-                }
-                CondUnit::Star => {
-                    let time: Decimal = time_unit.duration(val)?.into();
-                    let i = time / one_second_decimal();
-                    let mut init: Decimal = init_duration.into();
-                    init = init.checked_mul(i).ok_or(DError::OverflowError)?;
-                    init_duration = init.to_u64().ok_or(DError::OverflowError)?;
-                }
-            }
-        }
-        Ok((init_cond, init_duration))
+        Ok(nano_second)
     }
 }
 
@@ -405,7 +452,9 @@ impl Display for CondUnit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = match self {
             Self::Plus => PLUS.to_string(),
+            Self::Minus => MINUS.to_string(),
             Self::Star => STAR.to_string(),
+            Self::Slash => SLASH.to_string(),
         };
         write!(f, "{}", str)
     }
@@ -446,11 +495,100 @@ impl Display for CondUnit {
 /// // spaces are optional
 /// let duration = parse("1m * 10").unwrap();
 /// assert_eq!(duration,Duration::new(600,0));
+///
+/// // supports precedence and parentheses
+/// let duration = parse("(1h + 15m) * 3").unwrap();
+/// assert_eq!(duration,Duration::new(3*3600+45*60,0));
+///
+/// // supports division by a scalar
+/// let duration = parse("1d / 24").unwrap();
+/// assert_eq!(duration,Duration::new(3600,0));
 /// ```
-pub fn parse_std(input: impl AsRef<str>) -> Result<Duration, String> {
+pub fn parse_std(input: impl AsRef<str>) -> Result<Duration, DurationError> {
     parse(input.as_ref())
 }
 
+/// convert `Into<String>` to [`SignedDuration`]
+///
+/// Unlike [`parse_std`], the result may be negative.
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::{parse_signed, SignedDuration};
+///
+/// // supports a leading minus sign
+/// let duration = parse_signed("-5m").unwrap();
+/// assert_eq!(duration, SignedDuration::from_nanos(-5 * 60 * 1_000_000_000));
+///
+/// // supports subtraction
+/// let duration = parse_signed("1h - 10m").unwrap();
+/// assert_eq!(duration, SignedDuration::from_nanos(50 * 60 * 1_000_000_000));
+///
+/// let duration = parse_signed("10m - 1h").unwrap();
+/// assert_eq!(duration, SignedDuration::from_nanos(-50 * 60 * 1_000_000_000));
+/// ```
+pub fn parse_signed(input: impl AsRef<str>) -> Result<SignedDuration, DurationError> {
+    DurationParser::new().parse_signed(input)
+}
+
+/// convert `Into<String>` to a [`Span`]
+///
+/// Years, months, weeks and days are kept as calendar units instead of being counted as 365, 30,
+/// 7 and 1 days, and [`Span::apply_to`] adds them to a date on the calendar. A fraction of a unit
+/// is carried into the next smaller one, e.g. `1.5y` is one year and six months.
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::{parse_span, SignedDuration};
+///
+/// let span = parse_span("1y 2mon 3w 4d 5h").unwrap();
+/// assert_eq!((span.years(), span.months(), span.weeks(), span.days()), (1, 2, 3, 4));
+/// assert_eq!(span.time(), SignedDuration::from_nanos(5 * 3600 * 1_000_000_000));
+///
+/// let span = parse_span("1.5y").unwrap();
+/// assert_eq!((span.years(), span.months()), (1, 6));
+/// ```
+pub fn parse_span(input: impl AsRef<str>) -> Result<Span, DurationError> {
+    DurationParser::new().parse_span(input)
+}
+
+/// Divide one duration by another, e.g. how many `15m` slots fit into `1d`.
+///
+/// Inside an expression `/` divides a duration by a scalar (`1d / 24` is one hour),
+/// so a ratio of two durations is computed here instead. Both inputs accept the
+/// full expression syntax and the quotient is an exact [`Decimal`].
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::{parse_ratio, Decimal};
+///
+/// assert_eq!(parse_ratio("1d", "15m").unwrap(), Decimal::from(96));
+/// assert_eq!(parse_ratio("1h", "-2h").unwrap(), Decimal::new(-5, 1));
+/// assert_eq!(parse_ratio("1h", "0s").unwrap_err().to_string(), "division by zero");
+/// ```
+pub fn parse_ratio(
+    numerator: impl AsRef<str>,
+    denominator: impl AsRef<str>,
+) -> Result<Decimal, DurationError> {
+    let parser = DurationParser::new();
+    let (numerator, denominator) = (numerator.as_ref(), denominator.as_ref());
+    let dividend: Decimal = parser.parse_signed(numerator)?.as_nanos().into();
+    let divisor: Decimal = parser.parse_signed(denominator)?.as_nanos().into();
+    if divisor.is_zero() {
+        return Err(DurationError::from_error(
+            denominator,
+            DError::DivisionByZero,
+        ));
+    }
+    dividend.checked_div(divisor).ok_or_else(|| {
+        let expr = format!("{} / {}", numerator, denominator);
+        DurationError::from_error(&expr, DError::OverflowError)
+    })
+}
+
 /// convert `Into<String>` to `chrono::Duration`
 ///
 /// # Example
@@ -486,12 +624,14 @@ pub fn parse_std(input: impl AsRef<str>) -> Result<Duration, String> {
 /// // spaces are optional
 /// let duration = parse_chrono("1m * 10").unwrap();
 /// assert_eq!(duration,Duration::seconds(600));
+///
+/// // supports negative durations
+/// let duration = parse_chrono("1h - 61m").unwrap();
+/// assert_eq!(duration,Duration::seconds(-60));
 /// ```
 #[cfg(feature = "chrono")]
-pub fn parse_chrono(input: impl AsRef<str>) -> Result<chrono::Duration, String> {
-    let std_duration = parse_std(input)?;
-    let duration = chrono::Duration::from_std(std_duration).map_err(|e| e.to_string())?;
-    Ok(duration)
+pub fn parse_chrono(input: impl AsRef<str>) -> Result<chrono::Duration, DurationError> {
+    DurationParser::new().parse_chrono(input)
 }
 
 /// convert `Into<String>` to `time::Duration`
@@ -529,17 +669,72 @@ pub fn parse_chrono(input: impl AsRef<str>) -> Result<chrono::Duration, String>
 /// // spaces are optional
 /// let duration = parse_time("1m * 10").unwrap();
 /// assert_eq!(duration,Duration::seconds(600));
+///
+/// // supports negative durations
+/// let duration = parse_time("1h - 61m").unwrap();
+/// assert_eq!(duration,Duration::seconds(-60));
 /// ```
 #[cfg(feature = "time")]
-pub fn parse_time(input: impl AsRef<str>) -> Result<time::Duration, String> {
-    let std_duration = parse_std(input)?;
-    let duration = time::Duration::try_from(std_duration).map_err(|e| e.to_string())?;
-    Ok(duration)
+pub fn parse_time(input: impl AsRef<str>) -> Result<time::Duration, DurationError> {
+    DurationParser::new().parse_time(input)
+}
+
+/// convert `Into<String>` to `jiff::Span`
+///
+/// Years, months, weeks and days stay calendar units instead of being flattened to a fixed
+/// number of seconds, so jiff can add them to a date. A fraction of a unit is carried into the
+/// next smaller one, e.g. `1.5y` is one year and six months, and the time is kept in hours and
+/// smaller units. jiff spans have a single sign, so `1mon - 1d` is an error.
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::parse_jiff_span;
+/// use jiff::{civil::date, ToSpan};
+///
+/// let span = parse_jiff_span("1y 2mon 3d").unwrap();
+/// assert_eq!(span.fieldwise(), 1.year().months(2).days(3));
+///
+/// let span = parse_jiff_span("1.5y").unwrap();
+/// assert_eq!(span.fieldwise(), 1.year().months(6));
+///
+/// // a month is a calendar month once added to a date
+/// let end = date(2024, 1, 31).checked_add(parse_jiff_span("1mon").unwrap()).unwrap();
+/// assert_eq!(end, date(2024, 2, 29));
+///
+/// let span = parse_jiff_span("25h 30m").unwrap();
+/// assert_eq!(span.fieldwise(), 25.hours().minutes(30));
+///
+/// assert!(parse_jiff_span("1mon - 1d").is_err());
+/// ```
+#[cfg(feature = "jiff")]
+pub fn parse_jiff_span(input: impl AsRef<str>) -> Result<jiff::Span, DurationError> {
+    DurationParser::new().parse_jiff_span(input)
+}
+
+/// convert `Into<String>` to `jiff::SignedDuration`
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::parse_jiff_signed;
+/// use jiff::SignedDuration;
+///
+/// let duration = parse_jiff_signed("1m + 31").unwrap();
+/// assert_eq!(duration, SignedDuration::from_secs(91));
+///
+/// // supports negative durations
+/// let duration = parse_jiff_signed("1h - 61m").unwrap();
+/// assert_eq!(duration, SignedDuration::from_secs(-60));
+/// ```
+#[cfg(feature = "jiff")]
+pub fn parse_jiff_signed(input: impl AsRef<str>) -> Result<jiff::SignedDuration, DurationError> {
+    DurationParser::new().parse_jiff_signed(input)
 }
 
 #[cfg(feature = "chrono")]
 mod naive_date {
-    use crate::parse_chrono;
+    use crate::{parse_span, DError, DurationError};
     use chrono::Utc;
 
     #[allow(dead_code)]
@@ -548,30 +743,36 @@ mod naive_date {
         After,
     }
 
+    /// Add or subtract `input` from now, with years and months on the calendar, see
+    /// [`Span::apply_to`](crate::Span::apply_to).
     #[cfg(feature = "chrono")]
     pub fn calc_naive_date_time(
         input: impl AsRef<str>,
         history: TimeHistory,
-    ) -> Result<chrono::NaiveDateTime, String> {
-        let duration = parse_chrono(input)?;
-        let time = match history {
-            TimeHistory::Before => (Utc::now() - duration).naive_utc(),
-            TimeHistory::After => (Utc::now() + duration).naive_utc(),
+    ) -> Result<chrono::NaiveDateTime, DurationError> {
+        let input = input.as_ref();
+        let span = parse_span(input)?;
+        let span = match history {
+            TimeHistory::Before => -span,
+            TimeHistory::After => span,
         };
-        Ok(time)
+        span.apply_to(Utc::now().naive_utc())
+            .ok_or_else(|| DurationError::from_error(input, DError::OverflowError))
     }
 
     macro_rules! gen_naive_date_func {
         ($date_time:ident,$date:ident,$history:expr) => {
             #[allow(dead_code)]
             #[cfg(feature = "chrono")]
-            pub fn $date_time(input: impl AsRef<str>) -> Result<chrono::NaiveDateTime, String> {
+            pub fn $date_time(
+                input: impl AsRef<str>,
+            ) -> Result<chrono::NaiveDateTime, DurationError> {
                 calc_naive_date_time(input, $history)
             }
 
             #[allow(dead_code)]
             #[cfg(feature = "chrono")]
-            pub fn $date(input: impl AsRef<str>) -> Result<chrono::NaiveDate, String> {
+            pub fn $date(input: impl AsRef<str>) -> Result<chrono::NaiveDate, DurationError> {
                 let date: chrono::NaiveDateTime = calc_naive_date_time(input, $history)?;
                 Ok(date.date())
             }
@@ -1,31 +1,59 @@
-use crate::unit::{opt_unit_abbr, TimeUnit};
-use crate::{Calc, CondUnit, ExpectErr};
+use crate::expr::Expr;
+use crate::iso8601::{is_iso8601, parse_iso8601_nanos, parse_iso8601_span};
+use crate::span::SpanValue;
+use crate::unit::{opt_unit_abbr, quantity_nanos, TimeUnit};
+use crate::{round_nanos, CondUnit, DError, DResult, DurationError, DurationParser, ExpectErr};
+use rust_decimal::Decimal;
 use std::time::Duration;
-use winnow::ascii::{digit1, multispace0};
-use winnow::combinator::{alt, cut_err};
-use winnow::combinator::{eof, peek, repeat};
-use winnow::error::{ContextError, StrContext, StrContextValue};
+use winnow::ascii::{digit0, digit1, multispace0};
+use winnow::combinator::{alt, cut_err, delimited, fail, preceded, terminated};
+use winnow::combinator::{eof, opt, peek, repeat};
+use winnow::error::{ContextError, ErrMode, FromExternalError, StrContext, StrContextValue};
+use winnow::stream::AsChar;
+use winnow::token::take_while;
 use winnow::ModalResult as WResult;
 use winnow::Parser;
 
+pub(crate) const EXPECT_CLOCK: &str = "clock time, e.g. 01:30:00 or 05:30.250";
+
 pub(crate) fn cond_unit1(input: &mut &str) -> WResult<CondUnit> {
-    alt(('+'.value(CondUnit::Plus), '*'.value(CondUnit::Star)))
-        .context(StrContext::Expected(StrContextValue::Description(
-            CondUnit::get_expect_val(),
-        )))
-        .parse_next(input)
+    alt((
+        '+'.value(CondUnit::Plus),
+        '-'.value(CondUnit::Minus),
+        '*'.value(CondUnit::Star),
+        '/'.value(CondUnit::Slash),
+    ))
+    .context(StrContext::Expected(StrContextValue::Description(
+        CondUnit::get_expect_val(),
+    )))
+    .parse_next(input)
+}
+
+/// A decimal quantity such as `1`, `1.5`, `1.` or `.5`.
+pub(crate) fn number<'a>(input: &mut &'a str) -> WResult<&'a str> {
+    alt(((digit1, opt(('.', digit0))).take(), ('.', digit1).take())).parse_next(input)
+}
+
+/// A [`number`] with an optional leading minus sign, e.g. `-1.5`.
+pub(crate) fn signed_number<'a>(input: &mut &'a str) -> WResult<&'a str> {
+    (opt('-'), number).take().parse_next(input)
 }
 
+/// `+` or `-`. Two terms written next to each other (`3m 31s`) are added.
 fn opt_cond_unit(input: &mut &str) -> WResult<CondUnit> {
-    let result = cond_unit1.parse_next(input);
+    let result = cond_unit1
+        .verify(|op| matches!(op, CondUnit::Plus | CondUnit::Minus))
+        .parse_next(input);
     if result.is_err() {
         multispace0.parse_next(input)?;
-        if eof::<_, ContextError>.parse_next(input).is_ok() {
-            // The input result is empty except for spaces. Give `TimeUnit` default value
-            return Ok(CondUnit::Plus);
+        if eof::<_, ContextError>.parse_next(input).is_ok()
+            || peek::<_, _, ContextError, _>(')').parse_next(input).is_ok()
+        {
+            // Nothing left to add at this level.
+            return fail.parse_next(input);
         }
 
-        return cut_err(peek((digit1, multispace0, opt_unit_abbr)))
+        return cut_err(peek(alt((number.void(), '('.void()))))
             .context(StrContext::Expected(StrContextValue::Description(
                 CondUnit::get_expect_val(),
             )))
@@ -35,89 +63,279 @@ fn opt_cond_unit(input: &mut &str) -> WResult<CondUnit> {
     result
 }
 
-pub(crate) fn parse_expr_time(input: &mut &str) -> WResult<u64> {
-    (multispace0, digit1, multispace0, opt_unit_abbr, multispace0)
+/// `*` or `/`.
+fn mul_cond_unit(input: &mut &str) -> WResult<CondUnit> {
+    cond_unit1
+        .verify(|op| matches!(op, CondUnit::Star | CondUnit::Slash))
+        .parse_next(input)
+}
+
+/// A single quantity and its unit, e.g. `1.5` and hours for `1.5h`.
+fn quantity_unit<'a>(input: &mut &'a str, parser: &DurationParser) -> WResult<(&'a str, TimeUnit)> {
+    (
+        multispace0,
+        signed_number,
+        multispace0,
+        |input: &mut &str| opt_unit_abbr(input, parser),
+        multispace0,
+    )
         .map(|x| (x.1, x.3))
-        .try_map(|(v, unit)| unit.duration(v))
         .parse_next(input)
 }
 
-pub(crate) fn cond_time<'a>(input: &mut &'a str) -> WResult<Vec<(&'a str, CondUnit, TimeUnit)>> {
-    repeat(
-        0..,
+/// A single quantity with an optional unit, as an exact amount of nanoseconds.
+pub(crate) fn parse_expr_time(input: &mut &str, parser: &DurationParser) -> WResult<Decimal> {
+    let start = *input;
+    let (v, unit) = quantity_unit(input, parser)?;
+    quantity_nanos(v, parser.unit_nanos(unit)).map_err(|err| quantity_error(input, start, err))
+}
+
+/// A single quantity as an expression leaf; calendar units keep their quantity for spans.
+fn quantity(input: &mut &str, parser: &DurationParser) -> WResult<Expr> {
+    let start = *input;
+    let (v, unit) = quantity_unit(input, parser)?;
+    let nanos = quantity_nanos(v, parser.unit_nanos(unit));
+    let expr = if unit.is_calendar() {
+        quantity_nanos(v, Decimal::ONE)
+            .and_then(|quantity| Ok(Expr::Calendar(quantity, unit, nanos?)))
+    } else {
+        nanos.map(Expr::Value)
+    };
+    expr.map_err(|err| quantity_error(input, start, err))
+}
+
+/// Fail at the start of a quantity that does not fit, without backtracking, so the overflow is
+/// reported rather than the input left over after it.
+fn quantity_error<'a>(input: &mut &'a str, start: &'a str, err: DError) -> ErrMode<ContextError> {
+    *input = start;
+    ErrMode::from_external_error(input, err).cut()
+}
+
+/// Two-digit field of a clock time.
+fn clock_field<'a>(input: &mut &'a str) -> WResult<&'a str> {
+    take_while(2, AsChar::is_dec_digit).parse_next(input)
+}
+
+fn clock_nanos(
+    negative: bool,
+    days: Option<&str>,
+    first: &str,
+    second: &str,
+    third: Option<&str>,
+    fraction: &str,
+) -> DResult<Decimal> {
+    let (hours, minutes, seconds) = match third {
+        Some(third) => (Some(first), second, third),
+        None if days.is_some() => {
+            return Err(DError::ParseError(
+                "a day prefix must be followed by hh:mm:ss".to_string(),
+            ))
+        }
+        None => (None, first, second),
+    };
+    // Only the leading field may exceed its natural range, e.g. `90:00` is 90 minutes.
+    let field = |s: &str| s.parse::<u64>().unwrap_or(u64::MAX);
+    if days.is_some() && hours.is_some_and(|hours| field(hours) >= 24) {
+        return Err(DError::ParseError("hours must be less than 24".to_string()));
+    }
+    if hours.is_some() && field(minutes) >= 60 {
+        return Err(DError::ParseError(
+            "minutes must be less than 60".to_string(),
+        ));
+    }
+    if field(seconds) >= 60 {
+        return Err(DError::ParseError(
+            "seconds must be less than 60".to_string(),
+        ));
+    }
+
+    let nanos = [
+        days.map(|days| TimeUnit::Day.nanos(days)),
+        hours.map(|hours| TimeUnit::Hour.nanos(hours)),
+        Some(TimeUnit::Minute.nanos(minutes)),
+        Some(TimeUnit::Second.nanos(format!("{}{}", seconds, fraction))),
+    ]
+    .into_iter()
+    .flatten()
+    .try_fold(Decimal::ZERO, |acc, nanos| {
+        acc.checked_add(nanos?).ok_or(DError::OverflowError)
+    })?;
+    Ok(if negative { -nanos } else { nanos })
+}
+
+/// Clock notation, as an exact amount of nanoseconds:
+/// `hh:mm:ss[.fff]`, `mm:ss[.fff]` or the .NET style `d.hh:mm:ss[.fff]`.
+fn clock(input: &mut &str) -> WResult<Decimal> {
+    peek((opt('-'), opt((digit1, '.')), digit1, ':')).parse_next(input)?;
+    cut_err(
         (
-            multispace0,
-            opt_cond_unit,
-            multispace0,
+            opt('-'),
+            opt(terminated(digit1, '.')),
             digit1,
-            multispace0,
-            // Add by default.
-            // Parse unit, default is seconds.
-            opt_unit_abbr,
-            multispace0,
+            preceded(':', clock_field),
+            opt(preceded(':', clock_field)),
+            opt(('.', digit1)).take(),
         )
-            .map(|x| (x.3, x.1, x.5)),
+            .try_map(|(sign, days, first, second, third, fraction)| {
+                clock_nanos(sign.is_some(), days, first, second, third, fraction)
+            }),
     )
-    .fold(Vec::new, |mut acc: Vec<_>, item| {
-        acc.push(item);
-        acc
-    })
+    .context(StrContext::Expected(StrContextValue::Description(
+        EXPECT_CLOCK,
+    )))
     .parse_next(input)
 }
 
-pub fn parse(input: impl AsRef<str>) -> Result<Duration, String> {
-    let input = input.as_ref();
-    if input.is_empty() {
-        return Err(String::from("Empty input"));
+/// `factor := '-' factor | '(' expr ')' | clock | quantity`
+fn factor(input: &mut &str, parser: &DurationParser) -> WResult<Expr> {
+    delimited(
+        multispace0,
+        alt((
+            preceded('-', cut_err(|input: &mut &str| factor(input, parser)))
+                .map(|expr| Expr::Neg(Box::new(expr))),
+            delimited(
+                '(',
+                cut_err(|input: &mut &str| expr(input, parser)),
+                cut_err(')').context(StrContext::Expected(StrContextValue::CharLiteral(')'))),
+            ),
+            clock.map(Expr::Value),
+            |input: &mut &str| quantity(input, parser),
+        )),
+        multispace0,
+    )
+    .parse_next(input)
+}
+
+/// `term := factor (('*' | '/') factor)*`
+fn term(input: &mut &str, parser: &DurationParser) -> WResult<Expr> {
+    let mut acc = factor(input, parser)?;
+    // the right operand of `*` and `/` is a scalar, which needs no unit even in strict mode
+    let scalar_parser = parser.strict(false);
+    while let Some(op) = opt(mul_cond_unit).parse_next(input)? {
+        let rhs = cut_err(|input: &mut &str| factor(input, &scalar_parser)).parse_next(input)?;
+        acc = Expr::binary(acc, op, rhs);
+    }
+    Ok(acc)
+}
+
+/// `expr := term (('+' | '-')? term)*`
+pub(crate) fn expr(input: &mut &str, parser: &DurationParser) -> WResult<Expr> {
+    let mut acc = term(input, parser)?;
+    while let Some(op) = opt(opt_cond_unit).parse_next(input)? {
+        let rhs = cut_err(|input: &mut &str| term(input, parser)).parse_next(input)?;
+        acc = Expr::binary(acc, op, rhs);
     }
+    Ok(acc)
+}
 
-    #[cfg(feature = "no_calc")]
-    {
-        use crate::DError;
+pub fn parse(input: impl AsRef<str>) -> Result<Duration, DurationError> {
+    DurationParser::new().parse(input)
+}
 
-        let d = repeat(0.., parse_expr_time)
-            .try_fold(
-                Default::default,
-                |mut acc: u64, item| -> Result<_, DError> {
-                    acc = acc.checked_add(item).ok_or(DError::OverflowError)?;
-                    Ok(acc)
-                },
-            )
-            .parse(input)
-            .map_err(|err| err.to_string())?;
-        Ok(Duration::from_nanos(d))
+/// `std::time::Duration` results are limited to what fits into `u64` nanoseconds.
+pub(crate) fn nanos_to_std(nanos: i128) -> Result<Duration, DError> {
+    if nanos < 0 {
+        return Err(DError::NegativeError);
     }
+    let nanos = u64::try_from(nanos).map_err(|_| DError::OverflowError)?;
+    Ok(Duration::from_nanos(nanos))
+}
 
-    #[cfg(not(feature = "no_calc"))]
-    {
-        let (unit_time, cond_val) = (parse_expr_time, cond_time)
+/// Parse and evaluate `input` to a signed count of nanoseconds.
+pub(crate) fn parse_nanos(input: &str, parser: &DurationParser) -> Result<i128, DurationError> {
+    if input.is_empty() {
+        return Err(DurationError::empty());
+    }
+
+    if is_iso8601(input) {
+        return parse_iso8601_nanos(input, parser);
+    }
+
+    let nanos = if parser.calc {
+        (|input: &mut &str| expr(input, parser))
             .parse(input)
-            .map_err(|e| format!("{}", e))?;
+            .map_err(|err| DurationError::from_parse_error(err).suggest_unit(parser))?
+            .eval(parser.unit_nanos(parser.default_unit))
+            .map_err(|err| DurationError::from_error(input, err))?
+    } else {
+        repeat(
+            0..,
+            alt((
+                delimited(multispace0, clock, multispace0),
+                |input: &mut &str| parse_expr_time(input, parser),
+            )),
+        )
+        .try_fold(
+            Default::default,
+            |acc: Decimal, item| -> Result<_, DError> {
+                acc.checked_add(item).ok_or(DError::OverflowError)
+            },
+        )
+        .parse(input)
+        .map_err(|err| DurationError::from_parse_error(err).suggest_unit(parser))?
+    };
+
+    round_nanos(nanos).map_err(|err| DurationError::from_error(input, err))
+}
+
+/// Parse and evaluate `input` like [`parse_nanos`], but keep calendar units apart.
+pub(crate) fn parse_span_value(
+    input: &str,
+    parser: &DurationParser,
+) -> Result<SpanValue, DurationError> {
+    if input.is_empty() {
+        return Err(DurationError::empty());
+    }
 
-        let (init_cond, init_duration) = if cond_val.is_empty() {
-            CondUnit::init()
-        } else {
-            cond_val.calc().map_err(|err| err.to_string())?
-        };
+    if is_iso8601(input) {
+        return parse_iso8601_span(input, parser);
+    }
 
-        let duration = init_cond
-            .calc(unit_time, init_duration)
-            .map_err(|err| err.to_string())?;
-        Ok(duration)
+    let expr = if parser.calc {
+        (|input: &mut &str| expr(input, parser)).parse(input)
+    } else {
+        repeat(
+            0..,
+            alt((
+                delimited(multispace0, clock, multispace0).map(Expr::Value),
+                |input: &mut &str| quantity(input, parser),
+            )),
+        )
+        .fold(
+            || Expr::Value(Decimal::ZERO),
+            |acc, item| Expr::binary(acc, CondUnit::Plus, item),
+        )
+        .parse(input)
     }
+    .map_err(|err| DurationError::from_parse_error(err).suggest_unit(parser))?;
+    expr.eval_span(parser)
+        .map_err(|err| DurationError::from_error(input, err))
 }
 
 #[cfg(test)]
 #[allow(clippy::identity_op)]
 mod tests {
     use super::*;
-    use crate::{catch_err, unit::TimeUnit, CondUnit};
+    use crate::{catch_err, CondUnit, ErrorKind};
+
+    // shorthands with the default parser configuration
+    fn parse_expr_time(input: &mut &str) -> WResult<Decimal> {
+        super::parse_expr_time(input, &DurationParser::new())
+    }
+
+    fn expr(input: &mut &str) -> WResult<Expr> {
+        super::expr(input, &DurationParser::new())
+    }
+
+    fn parse_nanos(input: &str) -> Result<i128, DurationError> {
+        super::parse_nanos(input, &DurationParser::new())
+    }
 
     #[test]
     fn test_parse_expr_time() {
         let (input, val) = parse_expr_time.parse_peek("123m").unwrap();
         assert_eq!(input, "");
-        assert_eq!(val, 7380000000000);
+        assert_eq!(val, Decimal::from(7380000000000u64));
     }
 
     #[test]
@@ -128,22 +346,50 @@ mod tests {
     }
 
     #[test]
-    fn test_cond_time() {
-        let (input, out) = cond_time.parse_peek(" * 60").unwrap();
+    fn test_expr() {
+        let minutes = |n: u64| Expr::Value(Decimal::from(n * 60 * 1000 * 1000 * 1000));
+        let seconds = |n: u64| Expr::Value(Decimal::from(n * 1000 * 1000 * 1000));
+
+        let (input, out) = expr.parse_peek("1m * 60").unwrap();
         assert_eq!(input, "");
-        assert_eq!(out, vec![("60", CondUnit::Star, TimeUnit::Second)]);
-    }
+        assert_eq!(out, Expr::binary(minutes(1), CondUnit::Star, seconds(60)));
 
-    #[test]
-    fn test_cond_time2() {
-        let (input, out) = cond_time.parse_peek(" * 60*30").unwrap();
+        let (input, out) = expr.parse_peek(" 60m + 30m * 2").unwrap();
+        assert_eq!(input, "");
+        assert_eq!(
+            out,
+            Expr::binary(
+                minutes(60),
+                CondUnit::Plus,
+                Expr::binary(minutes(30), CondUnit::Star, seconds(2))
+            )
+        );
+
+        let (input, out) = expr.parse_peek("-(60m - 15m) / 3").unwrap();
+        assert_eq!(input, "");
+        assert_eq!(
+            out,
+            Expr::binary(
+                Expr::Neg(Box::new(Expr::binary(
+                    minutes(60),
+                    CondUnit::Minus,
+                    minutes(15)
+                ))),
+                CondUnit::Slash,
+                seconds(3)
+            )
+        );
+
+        // implicit addition binds like `+`
+        let (input, out) = expr.parse_peek("1m 30 * 2").unwrap();
         assert_eq!(input, "");
         assert_eq!(
             out,
-            vec![
-                ("60", CondUnit::Star, TimeUnit::Second),
-                ("30", CondUnit::Star, TimeUnit::Second),
-            ]
+            Expr::binary(
+                minutes(1),
+                CondUnit::Plus,
+                Expr::binary(seconds(30), CondUnit::Star, seconds(2))
+            )
         );
     }
 
@@ -194,6 +440,226 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_number() {
+        assert_eq!(number.parse_peek("15h"), Ok(("h", "15")));
+        assert_eq!(number.parse_peek("1.5h"), Ok(("h", "1.5")));
+        assert_eq!(number.parse_peek("1.h"), Ok(("h", "1.")));
+        assert_eq!(number.parse_peek(".5h"), Ok(("h", ".5")));
+        assert!(number.parse_peek(".h").is_err());
+    }
+
+    #[test]
+    fn test_parse_fraction() {
+        let duration = parse("1.5h").unwrap();
+        assert_eq!(duration, Duration::new(5400, 0));
+
+        let duration = parse("0.25d").unwrap();
+        assert_eq!(duration, Duration::new(6 * 60 * 60, 0));
+
+        let duration = parse("0.5").unwrap();
+        assert_eq!(duration, Duration::new(0, 500 * 1000 * 1000));
+
+        let duration = parse(".5m").unwrap();
+        assert_eq!(duration, Duration::new(30, 0));
+
+        let duration = parse("1h 0.5m 1.25ms").unwrap();
+        assert_eq!(duration, Duration::new(3630, 1250 * 1000));
+
+        // rounded to the nearest nanosecond, midpoints away from zero
+        let duration = parse("1.4ns").unwrap();
+        assert_eq!(duration, Duration::new(0, 1));
+        let duration = parse("0.0000000025s").unwrap();
+        assert_eq!(duration, Duration::new(0, 3));
+
+        #[cfg(not(feature = "no_calc"))]
+        {
+            let duration = parse("1.5m + 0.5").unwrap();
+            assert_eq!(duration, Duration::new(90, 500 * 1000 * 1000));
+
+            let duration = parse("1h * 1.5").unwrap();
+            assert_eq!(duration, Duration::new(5400, 0));
+        }
+    }
+
+    #[test]
+    fn test_parse_fraction_overflow() {
+        assert!(parse("584.9y").is_ok());
+        assert_eq!(catch_err!(parse("584.95y")), "overflow error");
+        assert_eq!(
+            catch_err!(parse("100000000000000000000y")),
+            r#"
+100000000000000000000y
+^
+overflow error"#
+                .trim()
+        );
+    }
+
+    #[test]
+    fn test_parse_nanos_signed() {
+        assert_eq!(parse_nanos("-5m"), Ok(-300 * 1000 * 1000 * 1000));
+        assert_eq!(parse_nanos(" -1.5s "), Ok(-1500 * 1000 * 1000));
+        assert_eq!(parse_nanos("-1m -30s"), Ok(-90 * 1000 * 1000 * 1000));
+
+        #[cfg(not(feature = "no_calc"))]
+        {
+            assert_eq!(parse_nanos("1h - 10m"), Ok(50 * 60 * 1000 * 1000 * 1000));
+            assert_eq!(parse_nanos("10m-1h"), Ok(-50 * 60 * 1000 * 1000 * 1000));
+            assert_eq!(
+                parse_nanos("1h - 10m + 5m"),
+                Ok(55 * 60 * 1000 * 1000 * 1000)
+            );
+            assert_eq!(parse_nanos("1m - -30s"), Ok(90 * 1000 * 1000 * 1000));
+            assert_eq!(parse_nanos("-1m * 2"), Ok(-120 * 1000 * 1000 * 1000));
+            assert_eq!(parse_nanos("1m * -2"), Ok(-120 * 1000 * 1000 * 1000));
+        }
+
+        let duration = parse("-1m 90s").unwrap();
+        assert_eq!(duration, Duration::new(30, 0));
+    }
+
+    #[cfg(not(feature = "no_calc"))]
+    #[test]
+    fn test_parse_precedence() {
+        let duration = parse("2h + 30m * 2").unwrap();
+        assert_eq!(duration, Duration::new(3 * 3600, 0));
+
+        let duration = parse("(1h + 15m) * 3").unwrap();
+        assert_eq!(duration, Duration::new(3 * 3600 + 45 * 60, 0));
+
+        let duration = parse("1h - 10m * 3").unwrap();
+        assert_eq!(duration, Duration::new(30 * 60, 0));
+
+        let duration = parse("1m 30s * 2").unwrap();
+        assert_eq!(duration, Duration::new(120, 0));
+
+        let duration = parse("2 * (1m + 1m) / 4").unwrap();
+        assert_eq!(duration, Duration::new(60, 0));
+
+        let duration = parse("1d / 24").unwrap();
+        assert_eq!(duration, Duration::new(3600, 0));
+
+        let duration = parse("-(10m - 1h)").unwrap();
+        assert_eq!(duration, Duration::new(50 * 60, 0));
+
+        let duration = parse(" ( ( 1h ) ) ( 1m ) ").unwrap();
+        assert_eq!(duration, Duration::new(3660, 0));
+
+        // exact until the final rounding
+        let duration = parse("0.5ns * 3").unwrap();
+        assert_eq!(duration, Duration::new(0, 2));
+        let duration = parse("1h / 7 * 7").unwrap();
+        assert_eq!(duration, Duration::new(3600, 0));
+    }
+
+    #[cfg(not(feature = "no_calc"))]
+    #[test]
+    fn test_parse_expr_err() {
+        assert_eq!(
+            catch_err!(parse("(1h + 15m")),
+            r#"
+(1h + 15m
+         ^
+expected `)`"#
+                .trim()
+        );
+
+        assert_eq!(
+            catch_err!(parse("1h * ")),
+            r#"
+1h * 
+     ^
+"#
+            .trim_start()
+        );
+
+        assert_eq!(catch_err!(parse("1h / 0")), "division by zero");
+        assert_eq!(catch_err!(parse("1h / (1m - 60s)")), "division by zero");
+    }
+
+    #[cfg(not(feature = "no_calc"))]
+    #[test]
+    fn test_parse_division() {
+        let duration = parse("1h / 7").unwrap();
+        assert_eq!(duration, Duration::new(514, 285_714_286));
+
+        let duration = parse("1d / 24 / 2").unwrap();
+        assert_eq!(duration, Duration::new(1800, 0));
+
+        let duration = parse("1.5h / 0.5").unwrap();
+        assert_eq!(duration, Duration::new(3 * 3600, 0));
+
+        let duration = parse("90m / 1m").unwrap(); // `1m` counts as 60
+        assert_eq!(duration, Duration::new(90, 0));
+    }
+
+    #[test]
+    fn test_parse_clock() {
+        assert_eq!(parse("01:30:00"), Ok(Duration::new(5400, 0)));
+        assert_eq!(parse("1:30:00"), Ok(Duration::new(5400, 0)));
+        assert_eq!(parse("05:30"), Ok(Duration::new(330, 0)));
+        assert_eq!(parse("05:30.250"), Ok(Duration::new(330, 250_000_000)));
+        assert_eq!(parse("90:00"), Ok(Duration::new(5400, 0)));
+        assert_eq!(parse("36:00:00"), Ok(Duration::new(36 * 3600, 0)));
+        assert_eq!(parse("00:00:00.000000001"), Ok(Duration::new(0, 1)));
+        assert_eq!(
+            parse("2.03:00:00"),
+            Ok(Duration::new(2 * 86400 + 3 * 3600, 0))
+        );
+        assert_eq!(
+            parse("2d 03:00:00"),
+            Ok(Duration::new(2 * 86400 + 3 * 3600, 0))
+        );
+        assert_eq!(parse(" 01:00:00 1m "), Ok(Duration::new(3660, 0)));
+        assert_eq!(
+            crate::parse_signed("-01:30:00"),
+            Ok(crate::SignedDuration::from_nanos(-5_400_000_000_000))
+        );
+        #[cfg(not(feature = "no_calc"))]
+        {
+            assert_eq!(parse("01:30:00 + 5m"), Ok(Duration::new(5700, 0)));
+            assert_eq!(parse("00:10:00 * 3"), Ok(Duration::new(1800, 0)));
+            assert_eq!(parse("1h - 00:30"), Ok(Duration::new(3570, 0)));
+        }
+    }
+
+    #[test]
+    fn test_parse_clock_err() {
+        assert_eq!(
+            catch_err!(parse("01:75:00")),
+            r#"
+01:75:00
+^
+expected clock time, e.g. 01:30:00 or 05:30.250
+minutes must be less than 60"#
+                .trim()
+        );
+        assert!(catch_err!(parse("05:60")).ends_with("seconds must be less than 60"));
+        assert!(catch_err!(parse("2.24:00:00")).ends_with("hours must be less than 24"));
+        assert!(catch_err!(parse("2.03:00")).ends_with("a day prefix must be followed by hh:mm:ss"));
+        assert!(parse("1:2:3").is_err());
+        assert!(parse("01:").is_err());
+        assert!(parse("01:00:00:00").is_err());
+    }
+
+    #[test]
+    fn test_parse_ratio() {
+        use crate::parse_ratio;
+        assert_eq!(parse_ratio("1d", "1h"), Ok(Decimal::from(24)));
+        assert_eq!(
+            parse_ratio("1h", "7"),
+            Ok(Decimal::from(3600) / Decimal::from(7))
+        );
+        assert_eq!(parse_ratio("-30m", "1h"), Ok(Decimal::new(-5, 1)));
+        assert_eq!(parse_ratio("0s", "1h"), Ok(Decimal::ZERO));
+        assert_eq!(parse_ratio("1h", "3h"), Ok(Decimal::ONE / Decimal::from(3)));
+        let err = parse_ratio("1h", "0ns").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DivisionByZero);
+        assert_eq!(err.input(), "0ns");
+        assert!(parse_ratio("1h", "1x").is_err());
+    }
+
     #[cfg(feature = "cn_unit")]
     #[test]
     fn test_parse_unit_cn() {
@@ -253,18 +719,18 @@ mod tests {
     fn test_duration_err() {
         #[cfg(not(feature = "no_calc"))]
         assert_eq!(
-            catch_err!(parse("0m+3-5")),
+            catch_err!(parse("0m+3%5")),
             r#"
-0m+3-5
+0m+3%5
     ^
 expected ["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"]"#
                 .trim()
         );
         #[cfg(feature = "no_calc")]
         assert_eq!(
-            catch_err!(parse("0m3-5")),
+            catch_err!(parse("0m3%5")),
             r#"
-0m3-5
+0m3%5
    ^
 expected ["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"]"#
                 .trim()
@@ -281,14 +747,19 @@ expected ["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"]"#
 
         #[cfg(not(feature = "no_calc"))]
         assert_eq!(
-            catch_err!(parse("3ms-2ms")),
+            catch_err!(parse("3ms%2ms")),
             r#"
-3ms-2ms
+3ms%2ms
    ^
-expected ['+', '*']"#
+expected ['+', '-', '*', '/']"#
                 .trim()
         );
 
+        assert_eq!(
+            catch_err!(parse("-3ms")),
+            "negative duration is not supported by std::time::Duration"
+        );
+
         assert_eq!(catch_err!(parse("")), "Empty input");
     }
 
@@ -389,7 +860,7 @@ overflow error"#
     #[test]
     fn test_overflow_mul() {
         let err = parse("580y*2").err().unwrap();
-        assert_eq!(err, "overflow error");
+        assert_eq!(err.to_string(), "overflow error");
     }
 
     #[test]
@@ -468,6 +939,18 @@ mod chrono_tests {
         assert_eq!(duration, Duration::seconds(144))
     }
 
+    #[test]
+    fn test_parse_chrono_negative() {
+        use chrono::Duration;
+        let duration = parse_chrono("-1m -30s").unwrap();
+        assert_eq!(duration, Duration::seconds(-90));
+        #[cfg(not(feature = "no_calc"))]
+        {
+            let duration = parse_chrono("1h - 61m").unwrap();
+            assert_eq!(duration, Duration::minutes(-1));
+        }
+    }
+
     #[test]
     fn test_after_naive_date_time() {
         let date = Utc::now().naive_utc().date();
@@ -506,6 +989,17 @@ mod time_tests {
     use crate::parse_time;
     use time::Duration;
 
+    #[test]
+    fn test_parse_time_negative() {
+        let duration = parse_time("-1m -30s").unwrap();
+        assert_eq!(duration, Duration::seconds(-90));
+        #[cfg(not(feature = "no_calc"))]
+        {
+            let duration = parse_time("1h - 61m").unwrap();
+            assert_eq!(duration, Duration::minutes(-1));
+        }
+    }
+
     #[test]
     fn test_parse_time() {
         #[cfg(not(feature = "no_calc"))]
@@ -0,0 +1,235 @@
+use crate::unit::{CN_UNIT_ALIASES, UNIT_ALIASES};
+use crate::DurationParser;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+/// An ECMA 262 regular expression for the input `parser` accepts.
+///
+/// Unit names come from the alias table, so the pattern follows `case_sensitive` and `cn_unit`.
+/// It checks the tokens, not the grammar: unbalanced parentheses or a dangling operator still
+/// match and are only rejected when parsing.
+fn duration_pattern(parser: &DurationParser) -> String {
+    let cn_aliases: &[_] = if parser.cn_unit {
+        &CN_UNIT_ALIASES
+    } else {
+        &[]
+    };
+    let mut aliases: Vec<&str> = UNIT_ALIASES
+        .iter()
+        .chain(cn_aliases)
+        .filter(|(_, unit)| parser.allows(*unit))
+        .map(|(alias, _)| *alias)
+        .collect();
+    // longest first, so `ms` is not read as `m` followed by junk
+    aliases.sort_by_key(|alias| std::cmp::Reverse(alias.chars().count()));
+    let units = aliases
+        .iter()
+        .map(|alias| {
+            alias
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' if !parser.case_sensitive => {
+                        format!("[{}{}]", c, c.to_ascii_uppercase())
+                    }
+                    c => c.to_string(),
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("|");
+
+    let number = r"(?:\d+(?:\.\d*)?|\.\d+)";
+    let clock = r"(?:\d+\.)?\d+(?::\d+){1,2}(?:\.\d+)?";
+    let operators = if parser.calc { r"[-+*/()]" } else { r"[-+]" };
+    let iso8601 = r"\s*[-+]?[Pp][0-9.,YMWDTHSymwdths]+\s*";
+    format!(
+        r"^(?:{iso8601}|(?:\s|{operators}|{clock}|{number}\s*(?:{units})?)+)$",
+        iso8601 = iso8601,
+        operators = operators,
+        clock = clock,
+        number = number,
+        units = units,
+    )
+}
+
+/// The schema of a duration string, or a plain number, read by `parser`.
+fn duration_schema(parser: &DurationParser, signed: bool) -> Schema {
+    let mut description = String::from(
+        "A duration such as `1h 30m`, `1.5d` or `90s`. Units: y, mon, w, d, h, m, s, ms, µs, \
+         us, ns and their long forms, e.g. `hours` or `milliseconds`.",
+    );
+    description.push_str(&format!(
+        " Numbers without a unit are read in `{}`.",
+        parser.default_unit
+    ));
+    if parser.calc {
+        description.push_str(
+            " Terms are added, and `+`, `-`, `*`, `/` and parentheses are evaluated, e.g. \
+             `(1h + 15m) * 2`; `*` and `/` take plain numbers.",
+        );
+    } else {
+        description.push_str(" Terms written next to each other are added, e.g. `1h 30m`.");
+    }
+    description.push_str(" Clock times such as `01:30:00` and ISO 8601 durations such as `PT1H30M` are accepted too.");
+    if !signed {
+        description.push_str(" The duration must not be negative.");
+    }
+
+    let mut examples = vec!["1h 30m", "90s", "01:30:00", "PT1H30M"];
+    if parser.calc {
+        examples.insert(2, "(1h + 15m) * 2");
+    }
+
+    json_schema!({
+        "type": ["string", "number"],
+        "pattern": duration_pattern(parser),
+        "description": description,
+        "examples": examples,
+    })
+}
+
+/// Internal macro to implement JsonSchema for a duration newtype
+macro_rules! impl_json_schema {
+    ($name:ident, $signed:expr) => {
+        impl JsonSchema for crate::$name {
+            fn schema_name() -> Cow<'static, str> {
+                stringify!($name).into()
+            }
+
+            fn schema_id() -> Cow<'static, str> {
+                concat!("duration_str::", stringify!($name)).into()
+            }
+
+            fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+                duration_schema(&DurationParser::new(), $signed)
+            }
+        }
+    };
+}
+
+impl_json_schema!(HumanDuration, false);
+
+#[cfg(feature = "chrono")]
+impl_json_schema!(HumanChronoDuration, true);
+
+#[cfg(feature = "time")]
+impl_json_schema!(HumanTimeDuration, true);
+
+/// For `#[schemars(with = "DurationStr")]`. The schema allows negative durations, which only
+/// signed duration types accept.
+#[cfg(feature = "serde_with")]
+impl<U: crate::DefaultUnit> JsonSchema for crate::DurationStr<U> {
+    fn schema_name() -> Cow<'static, str> {
+        match U::UNIT {
+            crate::TimeUnit::Second => "DurationStr".into(),
+            unit => format!("DurationStr_{:?}", unit).into(),
+        }
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        format!("duration_str::DurationStr<{:?}>", U::UNIT).into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        duration_schema(&DurationParser::new().default_unit(U::UNIT), true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HumanDuration, TimeUnit};
+
+    fn matches(pattern: &str, input: &str) -> bool {
+        regex_lite::Regex::new(pattern).unwrap().is_match(input)
+    }
+
+    #[test]
+    fn test_duration_pattern() {
+        let parser = DurationParser::new().case_sensitive(false);
+        let pattern = duration_pattern(&parser);
+        for input in [
+            "1h",
+            "1h 30m",
+            "1H30MIN",
+            "1.5d",
+            ".5s",
+            "90",
+            "3 milliseconds 2µs",
+            "01:30:00",
+            "2.03:00:00",
+            "05:30.250",
+            "PT1H30M",
+            "-P1,5D",
+            "-1m -30s",
+        ] {
+            assert!(parser.parse_signed(input).is_ok(), "{input}");
+            assert!(matches(&pattern, input), "{input}");
+        }
+        for input in ["", "1x", "1 fortnight", "h", "1h;"] {
+            assert!(parser.parse_signed(input).is_err(), "{input}");
+            assert!(!matches(&pattern, input), "{input}");
+        }
+
+        let pattern = duration_pattern(&parser.calc(true));
+        assert!(matches(&pattern, "(1h + 15m) * 2"));
+        let pattern = duration_pattern(&parser.calc(false));
+        assert!(!matches(&pattern, "(1h + 15m) * 2"));
+        let pattern = duration_pattern(&parser.case_sensitive(true));
+        assert!(!matches(&pattern, "1H"));
+        let pattern = duration_pattern(&parser.cn_unit(true));
+        assert!(matches(&pattern, "1时30分"));
+        let pattern = duration_pattern(&parser.allowed_units([TimeUnit::Second]));
+        assert!(matches(&pattern, "90s"));
+        assert!(!matches(&pattern, "1h"));
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = schemars::schema_for!(HumanDuration);
+        let schema = schema.as_value();
+        assert_eq!(schema["title"], "HumanDuration");
+        assert_eq!(schema["type"], serde_json::json!(["string", "number"]));
+        assert_eq!(schema["pattern"], duration_pattern(&DurationParser::new()));
+        assert_eq!(schema["examples"][0], "1h 30m");
+        let description = schema["description"].as_str().unwrap();
+        assert!(description.contains("Numbers without a unit are read in `s`."));
+        assert!(description.ends_with("The duration must not be negative."));
+    }
+
+    #[test]
+    fn test_json_schema_field() {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Config {
+            timeout: HumanDuration,
+            #[schemars(with = "HumanDuration")]
+            interval: std::time::Duration,
+            #[cfg(feature = "serde_with")]
+            #[schemars(with = "crate::DurationStr<crate::Millis>")]
+            delay: std::time::Duration,
+        }
+
+        let schema = schemars::schema_for!(Config);
+        let schema = schema.as_value();
+        assert_eq!(
+            schema["properties"]["timeout"]["$ref"],
+            "#/$defs/HumanDuration"
+        );
+        assert_eq!(
+            schema["properties"]["interval"]["$ref"],
+            "#/$defs/HumanDuration"
+        );
+        #[cfg(feature = "serde_with")]
+        {
+            assert_eq!(
+                schema["properties"]["delay"]["$ref"],
+                "#/$defs/DurationStr_MilliSecond"
+            );
+            let description = schema["$defs"]["DurationStr_MilliSecond"]["description"]
+                .as_str()
+                .unwrap();
+            assert!(description.contains("Numbers without a unit are read in `ms`."));
+        }
+    }
+}
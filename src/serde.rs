@@ -1,8 +1,7 @@
-#[cfg(all(feature = "chrono", feature = "serde"))]
-use crate::parse_chrono;
-use crate::parse_std;
-#[cfg(all(feature = "time", feature = "serde"))]
-use crate::parse_time;
+use crate::{DurationParser, HumanFormat, TimeUnit};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
 use std::time::Duration;
 
 #[cfg(all(feature = "chrono", feature = "serde"))]
@@ -16,104 +15,213 @@ use time::Duration as TDuration;
 pub trait DeserializeDuration<'de>: Sized {
     /// Deserialize this type from a duration string.
     fn deserialize_duration<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Self::deserialize_duration_with(deserializer, &DurationParser::new())
+    }
+
+    /// Deserialize this type from a duration string read by `parser`.
+    fn deserialize_duration_with<D>(
+        deserializer: D,
+        parser: &DurationParser,
+    ) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>;
 }
 
-/// Internal macro to implement DeserializeDuration for Duration and Option<Duration>
+/// Check a numeric value such as `30` or `1.5` against the policy of `parser` and spell it
+/// out, so it is read like a string in the parser's default unit.
+#[cfg(feature = "serde")]
+fn numeric_input<E>(parser: &DurationParser, number: String) -> Result<String, E>
+where
+    E: serde::de::Error,
+{
+    if !parser.numeric_negatives && number.starts_with('-') {
+        return Err(E::custom(format!(
+            "negative duration {} is not allowed",
+            number
+        )));
+    }
+    if !parser.numeric_fractions && number.contains('.') {
+        return Err(E::custom(format!(
+            "fractional duration {} is not allowed",
+            number
+        )));
+    }
+    Ok(number)
+}
+
+/// Internal macro to implement DeserializeDuration for Duration
 #[cfg(feature = "serde")]
 macro_rules! impl_deserialize_duration {
     ($duration_type:ty, $parse:ident) => {
         impl<'de> DeserializeDuration<'de> for $duration_type {
-            fn deserialize_duration<D>(deserializer: D) -> Result<Self, D::Error>
+            fn deserialize_duration_with<D>(
+                deserializer: D,
+                parser: &DurationParser,
+            ) -> Result<Self, D::Error>
             where
                 D: serde::Deserializer<'de>,
             {
-                struct DurationVisitor;
-                impl<'de> serde::de::Visitor<'de> for DurationVisitor {
+                struct DurationVisitor<'a>(&'a DurationParser);
+                impl<'de> serde::de::Visitor<'de> for DurationVisitor<'_> {
                     type Value = $duration_type;
 
                     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                        formatter.write_str("expect duration string, e.g: '1min+30'")
+                        formatter
+                            .write_str("expect duration string or number, e.g: '1min+30' or 90")
                     }
 
                     fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
                     where
                         E: serde::de::Error,
                     {
-                        let duration = $parse(s).map_err(serde::de::Error::custom)?;
+                        let duration = self.0.$parse(s).map_err(serde::de::Error::custom)?;
                         Ok(duration)
                     }
-                }
-
-                deserializer.deserialize_any(DurationVisitor)
-            }
-        }
-    };
-}
-
-/// Internal macro to implement DeserializeDuration for Option<Duration>
-#[cfg(feature = "serde")]
-macro_rules! impl_deserialize_option_duration {
-    ($duration_type:ty, $parse:ident) => {
-        impl<'de> DeserializeDuration<'de> for Option<$duration_type> {
-            fn deserialize_duration<D>(deserializer: D) -> Result<Self, D::Error>
-            where
-                D: serde::Deserializer<'de>,
-            {
-                struct OptionDurationVisitor;
-                impl<'de> serde::de::Visitor<'de> for OptionDurationVisitor {
-                    type Value = Option<$duration_type>;
-
-                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                        formatter.write_str("expect duration string, null, or missing field")
-                    }
 
-                    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+                    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
                     where
                         E: serde::de::Error,
                     {
-                        if s.is_empty() {
-                            return Ok(None);
-                        }
-                        let duration = $parse(s).map_err(serde::de::Error::custom)?;
-                        Ok(Some(duration))
+                        let s = numeric_input(self.0, v.to_string())?;
+                        self.visit_str(&s)
                     }
 
-                    fn visit_some<D>(self, d: D) -> Result<Self::Value, D::Error>
-                    where
-                        D: serde::Deserializer<'de>,
-                    {
-                        use serde::Deserialize;
-                        let s: String = String::deserialize(d)?;
-                        if s.is_empty() {
-                            return Ok(None);
-                        }
-                        let duration = $parse(s).map_err(serde::de::Error::custom)?;
-                        Ok(Some(duration))
-                    }
-
-                    fn visit_unit<E>(self) -> Result<Self::Value, E>
+                    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
                     where
                         E: serde::de::Error,
                     {
-                        Ok(None)
+                        let s = numeric_input(self.0, v.to_string())?;
+                        self.visit_str(&s)
                     }
 
-                    fn visit_none<E>(self) -> Result<Self::Value, E>
+                    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
                     where
                         E: serde::de::Error,
                     {
-                        Ok(None)
+                        let s = numeric_input(self.0, v.to_string())?;
+                        self.visit_str(&s)
                     }
                 }
 
-                deserializer.deserialize_option(OptionDurationVisitor)
+                deserializer.deserialize_any(DurationVisitor(parser))
             }
         }
     };
 }
 
+/// `None` for null, a missing field or an empty string, otherwise `Some` of whatever `T` reads,
+/// so `Option<Vec<Duration>>` and `Option<HashMap<_, Duration>>` work too.
+#[cfg(feature = "serde")]
+impl<'de, T> DeserializeDuration<'de> for Option<T>
+where
+    T: DeserializeDuration<'de>,
+{
+    fn deserialize_duration_with<D>(
+        deserializer: D,
+        parser: &DurationParser,
+    ) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+        use serde::de::IntoDeserializer;
+
+        /// Hands the value back to `T`, except for an empty string.
+        struct SomeVisitor<'a, T>(&'a DurationParser, PhantomData<T>);
+        impl<'de, T> serde::de::Visitor<'de> for SomeVisitor<'_, T>
+        where
+            T: DeserializeDuration<'de>,
+        {
+            type Value = Option<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("expect duration string, number, null, or missing field")
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                if s.is_empty() {
+                    return Ok(None);
+                }
+                T::deserialize_duration_with(s.into_deserializer(), self.0).map(Some)
+            }
+
+            fn visit_borrowed_str<E>(self, s: &'de str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                if s.is_empty() {
+                    return Ok(None);
+                }
+                T::deserialize_duration_with(s.into_deserializer(), self.0).map(Some)
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                T::deserialize_duration_with(v.into_deserializer(), self.0).map(Some)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                T::deserialize_duration_with(v.into_deserializer(), self.0).map(Some)
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                T::deserialize_duration_with(v.into_deserializer(), self.0).map(Some)
+            }
+
+            fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                T::deserialize_duration_with(SeqAccessDeserializer::new(seq), self.0).map(Some)
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                T::deserialize_duration_with(MapAccessDeserializer::new(map), self.0).map(Some)
+            }
+
+            fn visit_some<D>(self, d: D) -> Result<Self::Value, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                d.deserialize_any(self)
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(None)
+            }
+
+            fn visit_none<E>(self) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(None)
+            }
+        }
+
+        deserializer.deserialize_option(SomeVisitor(parser, PhantomData))
+    }
+}
+
 /// Deserialize duration string to Duration or Option<Duration>.
 ///
 /// This function works with both required and optional fields:
@@ -136,29 +244,495 @@ where
     T::deserialize_duration(deserializer)
 }
 
-// ==================== Implementations for std::time::Duration ====================
+/// The unit of bare numbers for [`deserialize_duration_with_default_unit`].
+#[cfg(feature = "serde")]
+pub trait DefaultUnit {
+    const UNIT: TimeUnit;
+}
+
+macro_rules! default_unit_markers {
+    ($($(#[$doc:meta])* $marker:ident => $unit:ident,)*) => {
+        $(
+            $(#[$doc])*
+            #[cfg(feature = "serde")]
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+            pub struct $marker;
+
+            #[cfg(feature = "serde")]
+            impl DefaultUnit for $marker {
+                const UNIT: TimeUnit = TimeUnit::$unit;
+            }
+        )*
+    };
+}
 
+default_unit_markers! {
+    /// Bare numbers are years.
+    Years => Year,
+    /// Bare numbers are months.
+    Months => Month,
+    /// Bare numbers are weeks.
+    Weeks => Week,
+    /// Bare numbers are days.
+    Days => Day,
+    /// Bare numbers are hours.
+    Hours => Hour,
+    /// Bare numbers are minutes.
+    Minutes => Minute,
+    /// Bare numbers are seconds, the default.
+    Seconds => Second,
+    /// Bare numbers are milliseconds.
+    Millis => MilliSecond,
+    /// Bare numbers are microseconds.
+    Micros => MicroSecond,
+    /// Bare numbers are nanoseconds.
+    Nanos => NanoSecond,
+}
+
+/// Deserialize duration string to Duration or Option<Duration>, reading bare numbers in the
+/// unit `U` instead of seconds.
+///
+/// ```ignore
+/// use duration_str::{deserialize_duration_with_default_unit, Millis};
+///
+/// // "500" is 500ms, "2s" is still two seconds
+/// #[serde(deserialize_with = "deserialize_duration_with_default_unit::<Millis, _, _>")]
+/// timeout: Duration,
+/// ```
 #[cfg(feature = "serde")]
-impl_deserialize_duration!(Duration, parse_std);
+pub fn deserialize_duration_with_default_unit<'de, U, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    U: DefaultUnit,
+    D: serde::Deserializer<'de>,
+    T: DeserializeDuration<'de>,
+{
+    T::deserialize_duration_with(deserializer, &DurationParser::new().default_unit(U::UNIT))
+}
+
+/// Deserialize duration string to Duration or Option<Duration>, rejecting numbers without a
+/// unit such as `"500"`. See [`DurationParser::strict`].
+///
+/// ```ignore
+/// #[serde(deserialize_with = "deserialize_duration_strict")]
+/// timeout: Duration,
+/// ```
+#[cfg(feature = "serde")]
+pub fn deserialize_duration_strict<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: DeserializeDuration<'de>,
+{
+    T::deserialize_duration_with(deserializer, &DurationParser::new().strict(true))
+}
 
+// ==================== Bounded durations ====================
+
+/// Trait for durations that [`deserialize_duration_bounded`] can check against its bounds.
 #[cfg(feature = "serde")]
-impl_deserialize_option_duration!(Duration, parse_std);
+pub trait DurationBounds {
+    /// Fail unless the duration lies within `min..=max` nanoseconds. `None` always passes.
+    fn check_bounds<E>(&self, min: u64, max: u64) -> Result<(), E>
+    where
+        E: serde::de::Error;
+}
+
+/// Internal macro to implement DurationBounds for a duration type
+#[cfg(feature = "serde")]
+macro_rules! impl_duration_bounds {
+    ($duration_type:ty, $nanos:expr) => {
+        impl DurationBounds for $duration_type {
+            fn check_bounds<E>(&self, min: u64, max: u64) -> Result<(), E>
+            where
+                E: serde::de::Error,
+            {
+                let nanos: i128 = $nanos(self);
+                if (min as i128..=max as i128).contains(&nanos) {
+                    return Ok(());
+                }
+                Err(E::custom(format!(
+                    "duration {} is out of range, expected between {} and {}",
+                    crate::HumanFormat::human_format(self),
+                    Duration::from_nanos(min).human_format(),
+                    Duration::from_nanos(max).human_format(),
+                )))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "serde")]
+impl_duration_bounds!(Duration, |d: &Duration| d.as_nanos() as i128);
+
+#[cfg(all(feature = "chrono", feature = "serde"))]
+impl_duration_bounds!(CDuration, |d: &CDuration| d.num_seconds() as i128
+    * crate::ONE_SECOND_NANOSECOND as i128
+    + d.subsec_nanos() as i128);
+
+#[cfg(all(feature = "time", feature = "serde"))]
+impl_duration_bounds!(TDuration, |d: &TDuration| d.whole_nanoseconds());
+
+#[cfg(all(feature = "jiff", feature = "serde"))]
+impl_duration_bounds!(jiff::SignedDuration, |d: &jiff::SignedDuration| d
+    .as_nanos());
+
+#[cfg(feature = "serde")]
+impl<T: DurationBounds> DurationBounds for Option<T> {
+    fn check_bounds<E>(&self, min: u64, max: u64) -> Result<(), E>
+    where
+        E: serde::de::Error,
+    {
+        match self {
+            Some(duration) => duration.check_bounds(min, max),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Deserialize duration string to Duration or Option<Duration>, rejecting durations outside
+/// `MIN..=MAX` nanoseconds with an error naming the bounds.
+///
+/// ```ignore
+/// // between 100ms and 5min
+/// #[serde(deserialize_with = "deserialize_duration_bounded::<100_000_000, 300_000_000_000, _, _>")]
+/// timeout: Duration,
+/// ```
+#[cfg(feature = "serde")]
+pub fn deserialize_duration_bounded<'de, const MIN: u64, const MAX: u64, D, T>(
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: DeserializeDuration<'de> + DurationBounds,
+{
+    let duration = T::deserialize_duration(deserializer)?;
+    duration.check_bounds(MIN, MAX)?;
+    Ok(duration)
+}
+
+/// A `std::time::Duration` within `MIN..=MAX` nanoseconds, checked when it is deserialized.
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::BoundedDuration;
+/// use std::time::Duration;
+///
+/// // between 100ms and 5min
+/// type Timeout = BoundedDuration<100_000_000, 300_000_000_000>;
+///
+/// let timeout: Timeout = serde_json::from_str(r#""30s""#).unwrap();
+/// assert_eq!(*timeout, Duration::from_secs(30));
+///
+/// let err = serde_json::from_str::<Timeout>(r#""10min""#).unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "duration 10min is out of range, expected between 100ms and 5min"
+/// );
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedDuration<const MIN: u64, const MAX: u64>(Duration);
+
+#[cfg(feature = "serde")]
+impl<const MIN: u64, const MAX: u64> BoundedDuration<MIN, MAX> {
+    /// The smallest duration accepted.
+    pub const MIN: Duration = Duration::from_nanos(MIN);
+    /// The largest duration accepted.
+    pub const MAX: Duration = Duration::from_nanos(MAX);
+
+    /// Wrap `duration`, or `None` if it is out of range.
+    pub fn new(duration: Duration) -> Option<Self> {
+        (Self::MIN..=Self::MAX)
+            .contains(&duration)
+            .then_some(BoundedDuration(duration))
+    }
+
+    /// The wrapped duration.
+    pub const fn into_inner(self) -> Duration {
+        self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const MIN: u64, const MAX: u64> std::ops::Deref for BoundedDuration<MIN, MAX> {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const MIN: u64, const MAX: u64> From<BoundedDuration<MIN, MAX>> for Duration {
+    fn from(duration: BoundedDuration<MIN, MAX>) -> Self {
+        duration.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const MIN: u64, const MAX: u64> std::fmt::Display for BoundedDuration<MIN, MAX> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.human_format())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const MIN: u64, const MAX: u64> serde::Serialize for BoundedDuration<MIN, MAX> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize_duration(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const MIN: u64, const MAX: u64> serde::Deserialize<'de> for BoundedDuration<MIN, MAX> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_duration_bounded::<MIN, MAX, _, _>(deserializer).map(BoundedDuration)
+    }
+}
+
+// ==================== Implementations for std::time::Duration ====================
+
+#[cfg(feature = "serde")]
+impl_deserialize_duration!(Duration, parse);
 
 // ==================== Implementations for chrono::Duration ====================
 
 #[cfg(all(feature = "chrono", feature = "serde"))]
 impl_deserialize_duration!(CDuration, parse_chrono);
 
-#[cfg(all(feature = "chrono", feature = "serde"))]
-impl_deserialize_option_duration!(CDuration, parse_chrono);
+// ==================== Implementations for time::Duration ====================
+
+#[cfg(all(feature = "time", feature = "serde"))]
+impl_deserialize_duration!(TDuration, parse_time);
+
+// ==================== Implementations for jiff ====================
+
+#[cfg(all(feature = "jiff", feature = "serde"))]
+impl_deserialize_duration!(jiff::SignedDuration, parse_jiff_signed);
+
+#[cfg(all(feature = "jiff", feature = "serde"))]
+impl_deserialize_duration!(jiff::Span, parse_jiff_span);
+
+// ==================== Implementations for collections ====================
+
+/// Deserialize one element of a collection with [`DeserializeDuration`].
+#[cfg(feature = "serde")]
+struct DurationSeed<'a, T>(&'a DurationParser, PhantomData<T>);
+
+#[cfg(feature = "serde")]
+impl<'a, T> DurationSeed<'a, T> {
+    fn new(parser: &'a DurationParser) -> Self {
+        DurationSeed(parser, PhantomData)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::de::DeserializeSeed<'de> for DurationSeed<'_, T>
+where
+    T: DeserializeDuration<'de>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize_duration_with(deserializer, self.0)
+    }
+}
+
+/// Read every element of a sequence as a duration.
+#[cfg(feature = "serde")]
+fn deserialize_duration_seq<'de, D, T>(
+    deserializer: D,
+    parser: &DurationParser,
+) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: DeserializeDuration<'de>,
+{
+    struct SeqVisitor<'a, T>(&'a DurationParser, PhantomData<T>);
+    impl<'de, T> serde::de::Visitor<'de> for SeqVisitor<'_, T>
+    where
+        T: DeserializeDuration<'de>,
+    {
+        type Value = Vec<T>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("expect a sequence of durations")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::SeqAccess<'de>,
+        {
+            let mut durations = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            while let Some(duration) = seq.next_element_seed(DurationSeed::new(self.0))? {
+                durations.push(duration);
+            }
+            Ok(durations)
+        }
+    }
+
+    deserializer.deserialize_seq(SeqVisitor(parser, PhantomData))
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> DeserializeDuration<'de> for Vec<T>
+where
+    T: DeserializeDuration<'de>,
+{
+    fn deserialize_duration_with<D>(
+        deserializer: D,
+        parser: &DurationParser,
+    ) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_duration_seq(deserializer, parser)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, const N: usize> DeserializeDuration<'de> for [T; N]
+where
+    T: DeserializeDuration<'de>,
+{
+    fn deserialize_duration_with<D>(
+        deserializer: D,
+        parser: &DurationParser,
+    ) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let durations: Vec<T> = deserialize_duration_seq(deserializer, parser)?;
+        let len = durations.len();
+        durations.try_into().map_err(|_| {
+            serde::de::Error::invalid_length(len, &format!("an array of length {}", N).as_str())
+        })
+    }
+}
+
+/// Internal macro to implement DeserializeDuration for maps with duration values
+#[cfg(feature = "serde")]
+macro_rules! impl_deserialize_duration_map {
+    ($map:ident<K, T $(, $hasher:ident)?>, $($key_bound:path),+) => {
+        impl<'de, K, T $(, $hasher)?> DeserializeDuration<'de> for $map<K, T $(, $hasher)?>
+        where
+            K: serde::Deserialize<'de> $(+ $key_bound)+,
+            T: DeserializeDuration<'de>,
+            $($hasher: BuildHasher + Default,)?
+        {
+            fn deserialize_duration_with<D>(
+                deserializer: D,
+                parser: &DurationParser,
+            ) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct MapVisitor<'a, M>(&'a DurationParser, PhantomData<M>);
+                impl<'de, K, T $(, $hasher)?> serde::de::Visitor<'de>
+                    for MapVisitor<'_, $map<K, T $(, $hasher)?>>
+                where
+                    K: serde::Deserialize<'de> $(+ $key_bound)+,
+                    T: DeserializeDuration<'de>,
+                    $($hasher: BuildHasher + Default,)?
+                {
+                    type Value = $map<K, T $(, $hasher)?>;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        formatter.write_str("expect a map of durations")
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: serde::de::MapAccess<'de>,
+                    {
+                        let mut durations = $map::default();
+                        while let Some(key) = map.next_key()? {
+                            let duration = map.next_value_seed(DurationSeed::new(self.0))?;
+                            durations.insert(key, duration);
+                        }
+                        Ok(durations)
+                    }
+                }
+
+                deserializer.deserialize_map(MapVisitor(parser, PhantomData))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "serde")]
+impl_deserialize_duration_map!(BTreeMap<K, T>, Ord);
+
+#[cfg(feature = "serde")]
+impl_deserialize_duration_map!(HashMap<K, T, S>, Eq, Hash);
+
+/// Internal macro to implement DeserializeDuration for tuples of durations
+#[cfg(feature = "serde")]
+macro_rules! impl_deserialize_duration_tuple {
+    ($len:expr => $($index:tt $name:ident)+) => {
+        impl<'de, $($name),+> DeserializeDuration<'de> for ($($name,)+)
+        where
+            $($name: DeserializeDuration<'de>,)+
+        {
+            fn deserialize_duration_with<D>(
+                deserializer: D,
+                parser: &DurationParser,
+            ) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct TupleVisitor<'a, T>(&'a DurationParser, PhantomData<T>);
+                impl<'de, $($name),+> serde::de::Visitor<'de> for TupleVisitor<'_, ($($name,)+)>
+                where
+                    $($name: DeserializeDuration<'de>,)+
+                {
+                    type Value = ($($name,)+);
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        write!(formatter, "expect a tuple of {} durations", $len)
+                    }
 
-// ==================== Implementations for time::Duration ====================
+                    #[allow(non_snake_case)]
+                    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: serde::de::SeqAccess<'de>,
+                    {
+                        $(
+                            let $name = seq
+                                .next_element_seed(DurationSeed::new(self.0))?
+                                .ok_or_else(|| serde::de::Error::invalid_length($index, &self))?;
+                        )+
+                        Ok(($($name,)+))
+                    }
+                }
 
-#[cfg(all(feature = "time", feature = "serde"))]
-impl_deserialize_duration!(TDuration, parse_time);
+                deserializer.deserialize_tuple($len, TupleVisitor(parser, PhantomData))
+            }
+        }
+    };
+}
 
-#[cfg(all(feature = "time", feature = "serde"))]
-impl_deserialize_option_duration!(TDuration, parse_time);
+#[cfg(feature = "serde")]
+impl_deserialize_duration_tuple!(1 => 0 T0);
+#[cfg(feature = "serde")]
+impl_deserialize_duration_tuple!(2 => 0 T0 1 T1);
+#[cfg(feature = "serde")]
+impl_deserialize_duration_tuple!(3 => 0 T0 1 T1 2 T2);
+#[cfg(feature = "serde")]
+impl_deserialize_duration_tuple!(4 => 0 T0 1 T1 2 T2 3 T3);
+#[cfg(feature = "serde")]
+impl_deserialize_duration_tuple!(5 => 0 T0 1 T1 2 T2 3 T3 4 T4);
+#[cfg(feature = "serde")]
+impl_deserialize_duration_tuple!(6 => 0 T0 1 T1 2 T2 3 T3 4 T4 5 T5);
 
 // ==================== Type-specific functions for convenience ====================
 
@@ -220,6 +794,147 @@ where
     DeserializeDuration::deserialize_duration(deserializer)
 }
 
+// ==================== Serialization ====================
+
+/// Trait for types that can be serialized to a duration string with [`HumanFormat`].
+///
+/// [`HumanFormat`]: crate::HumanFormat
+#[cfg(feature = "serde")]
+pub trait SerializeDuration {
+    /// Serialize this type as a duration string such as `"1h 30min"`.
+    fn serialize_duration<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer;
+}
+
+/// Internal macro to implement SerializeDuration for a type implementing HumanFormat
+#[cfg(all(
+    feature = "serde",
+    any(feature = "chrono", feature = "time", feature = "jiff")
+))]
+macro_rules! impl_serialize_duration {
+    ($duration_type:ty) => {
+        impl SerializeDuration for $duration_type {
+            fn serialize_duration<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str(&crate::HumanFormat::human_format(self))
+            }
+        }
+    };
+}
+
+/// Fails for durations above `u64::MAX` nanoseconds, about 584 years, which
+/// [`deserialize_duration`] could not read back.
+#[cfg(feature = "serde")]
+impl SerializeDuration for Duration {
+    fn serialize_duration<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.as_nanos() > u128::from(u64::MAX) {
+            return Err(serde::ser::Error::custom(crate::DError::OverflowError));
+        }
+        serializer.serialize_str(&crate::HumanFormat::human_format(self))
+    }
+}
+
+#[cfg(all(feature = "chrono", feature = "serde"))]
+impl_serialize_duration!(CDuration);
+
+#[cfg(all(feature = "time", feature = "serde"))]
+impl_serialize_duration!(TDuration);
+
+#[cfg(all(feature = "jiff", feature = "serde"))]
+impl_serialize_duration!(jiff::SignedDuration);
+
+#[cfg(all(feature = "jiff", feature = "serde"))]
+impl_serialize_duration!(jiff::Span);
+
+#[cfg(feature = "serde")]
+impl<T: SerializeDuration> SerializeDuration for Option<T> {
+    fn serialize_duration<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct SomeDuration<'a, T>(&'a T);
+        impl<T: SerializeDuration> serde::Serialize for SomeDuration<'_, T> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                self.0.serialize_duration(serializer)
+            }
+        }
+
+        match self {
+            Some(duration) => serializer.serialize_some(&SomeDuration(duration)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Serialize Duration or Option<Duration> to a duration string such as `"1h 30min"`, which
+/// [`deserialize_duration`] reads back to the same value. A `std::time::Duration` above
+/// `u64::MAX` nanoseconds, about 584 years, is too long to read back and fails to serialize.
+///
+/// ```ignore
+/// #[serde(serialize_with = "serialize_duration")]
+/// time_ticker: Duration,
+///
+/// #[serde(serialize_with = "serialize_duration")]
+/// time_ticker: Option<Duration>,
+/// ```
+#[cfg(feature = "serde")]
+pub fn serialize_duration<T, S>(duration: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: SerializeDuration,
+    S: serde::Serializer,
+{
+    duration.serialize_duration(serializer)
+}
+
+/// Serialize and deserialize Duration or Option<Duration> as a duration string, for
+/// `#[serde(with = "duration_str::serde_human")]`.
+///
+/// Serialization writes [`HumanFormat::human_format`](crate::HumanFormat::human_format) and
+/// deserialization is [`deserialize_duration`], so values round-trip. The one exception is a
+/// `std::time::Duration` above `u64::MAX` nanoseconds, about 584 years, which fails to
+/// serialize because it could not be read back.
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "duration_str::serde_human")]
+///     time_ticker: Duration,
+///     #[serde(default, with = "duration_str::serde_human")]
+///     timeout: Option<chrono::Duration>,
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub mod serde_human {
+    use super::{DeserializeDuration, SerializeDuration};
+
+    /// See [`serialize_duration`](super::serialize_duration).
+    pub fn serialize<T, S>(duration: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: SerializeDuration,
+        S: serde::Serializer,
+    {
+        duration.serialize_duration(serializer)
+    }
+
+    /// See [`deserialize_duration`](super::deserialize_duration).
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        T: DeserializeDuration<'de>,
+    {
+        T::deserialize_duration(deserializer)
+    }
+}
+
 #[cfg(all(test, feature = "time"))]
 mod tests {
     use super::*;
@@ -487,6 +1202,450 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_duration_with_default_unit() {
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Config {
+            #[serde(deserialize_with = "deserialize_duration_with_default_unit::<Millis, _, _>")]
+            timeout: std::time::Duration,
+            #[serde(
+                default,
+                deserialize_with = "deserialize_duration_with_default_unit::<Minutes, _, _>"
+            )]
+            interval: Option<TDuration>,
+        }
+
+        let json = r#"{"timeout":"500","interval":"90"}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                timeout: std::time::Duration::from_millis(500),
+                interval: Some(TDuration::minutes(90)),
+            }
+        );
+
+        let json = r#"{"timeout":"2s"}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.timeout, std::time::Duration::from_secs(2));
+        assert_eq!(config.interval, None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_duration_strict() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "deserialize_duration_strict")]
+            timeout: std::time::Duration,
+        }
+
+        let json = r#"{"timeout":"1m 30s"}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.timeout, std::time::Duration::from_secs(90));
+
+        let json = r#"{"timeout":"500"}"#;
+        let err = serde_json::from_str::<Config>(json).unwrap_err();
+        assert!(err.to_string().starts_with("500\n   ^\nexpected"), "{err}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_duration_number() {
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Config {
+            #[serde(deserialize_with = "deserialize_duration")]
+            timeout: std::time::Duration,
+            #[serde(default, deserialize_with = "deserialize_duration")]
+            interval: Option<TDuration>,
+            #[serde(
+                default,
+                deserialize_with = "deserialize_duration_with_default_unit::<Millis, _, _>"
+            )]
+            delay: Option<std::time::Duration>,
+        }
+
+        let json = r#"{"timeout":30,"interval":-1.5,"delay":250}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                timeout: std::time::Duration::from_secs(30),
+                interval: Some(TDuration::milliseconds(-1500)),
+                delay: Some(std::time::Duration::from_millis(250)),
+            }
+        );
+
+        let json = r#"{"timeout":0.25,"interval":null}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.timeout, std::time::Duration::from_millis(250));
+        assert_eq!(config.interval, None);
+
+        let json = r#"{"timeout":-30}"#;
+        let err = serde_json::from_str::<Config>(json).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("negative duration is not supported"),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_duration_number_policy() {
+        fn whole_positive<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+            T: DeserializeDuration<'de>,
+        {
+            let parser = DurationParser::new()
+                .numeric_negatives(false)
+                .numeric_fractions(false);
+            T::deserialize_duration_with(deserializer, &parser)
+        }
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "whole_positive")]
+            timeout: TDuration,
+        }
+
+        let config: Config = serde_json::from_str(r#"{"timeout":30}"#).unwrap();
+        assert_eq!(config.timeout, TDuration::seconds(30));
+        let config: Config = serde_json::from_str(r#"{"timeout":30.0}"#).unwrap();
+        assert_eq!(config.timeout, TDuration::seconds(30));
+        // strings are not numeric values
+        let config: Config = serde_json::from_str(r#"{"timeout":"-1.5m"}"#).unwrap();
+        assert_eq!(config.timeout, TDuration::seconds(-90));
+
+        let err = serde_json::from_str::<Config>(r#"{"timeout":-30}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("negative duration -30 is not allowed"),
+            "{err}"
+        );
+        let err = serde_json::from_str::<Config>(r#"{"timeout":1.5}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("fractional duration 1.5 is not allowed"),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_duration_collections() {
+        use std::time::Duration;
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "deserialize_duration")]
+            backoff: Vec<Duration>,
+            #[serde(deserialize_with = "deserialize_duration")]
+            buckets: [TDuration; 3],
+            #[serde(deserialize_with = "deserialize_duration")]
+            timeouts: HashMap<String, Duration>,
+            #[serde(deserialize_with = "deserialize_duration")]
+            windows: BTreeMap<u32, Option<Duration>>,
+            #[serde(deserialize_with = "deserialize_duration")]
+            range: (Duration, TDuration),
+        }
+
+        let json = r#"{
+            "backoff": ["100ms", 1, "1m"],
+            "buckets": ["1ms", "10ms", "-1s"],
+            "timeouts": {"/login": "5s", "/upload": "2m"},
+            "windows": {"1": "1h", "2": null},
+            "range": ["1s", "-1m"]
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.backoff,
+            [
+                Duration::from_millis(100),
+                Duration::from_secs(1),
+                Duration::from_secs(60)
+            ]
+        );
+        assert_eq!(
+            config.buckets,
+            [
+                TDuration::milliseconds(1),
+                TDuration::milliseconds(10),
+                TDuration::seconds(-1)
+            ]
+        );
+        assert_eq!(config.timeouts["/login"], Duration::from_secs(5));
+        assert_eq!(config.timeouts["/upload"], Duration::from_secs(120));
+        assert_eq!(
+            config.windows,
+            BTreeMap::from([(1, Some(Duration::from_secs(3600))), (2, None)])
+        );
+        assert_eq!(
+            config.range,
+            (Duration::from_secs(1), TDuration::seconds(-60))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_option_duration_collections() {
+        use std::time::Duration;
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Config {
+            #[serde(default, deserialize_with = "deserialize_duration")]
+            backoff: Option<Vec<Duration>>,
+            #[serde(default, deserialize_with = "deserialize_duration")]
+            timeouts: Option<HashMap<String, Duration>>,
+            #[serde(default, deserialize_with = "deserialize_duration")]
+            range: Option<[Duration; 2]>,
+        }
+
+        let json = r#"{
+            "backoff": ["100ms", 1],
+            "timeouts": {"/login": "5s"},
+            "range": ["1s", "1m"]
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.backoff,
+            Some(vec![Duration::from_millis(100), Duration::from_secs(1)])
+        );
+        assert_eq!(
+            config.timeouts,
+            Some(HashMap::from([(
+                "/login".to_string(),
+                Duration::from_secs(5)
+            )]))
+        );
+        assert_eq!(
+            config.range,
+            Some([Duration::from_secs(1), Duration::from_secs(60)])
+        );
+
+        let json = r#"{"backoff": null, "timeouts": ""}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.backoff, None);
+        assert_eq!(config.timeouts, None);
+        assert_eq!(config.range, None);
+
+        let err = serde_json::from_str::<Config>(r#"{"backoff": ["1x"]}"#).unwrap_err();
+        assert!(err.to_string().contains("expected"), "{err}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_duration_collections_err() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Buckets {
+            #[serde(deserialize_with = "deserialize_duration")]
+            #[allow(dead_code)]
+            buckets: [std::time::Duration; 2],
+        }
+        let err = serde_json::from_str::<Buckets>(r#"{"buckets":["1s"]}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid length 1, expected an array of length 2"),
+            "{err}"
+        );
+        let err = serde_json::from_str::<Buckets>(r#"{"buckets":["1s","1x"]}"#).unwrap_err();
+        assert!(err.to_string().starts_with("1x\n ^\nexpected"), "{err}");
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Range {
+            #[serde(deserialize_with = "deserialize_duration")]
+            #[allow(dead_code)]
+            range: (std::time::Duration, std::time::Duration),
+        }
+        let err = serde_json::from_str::<Range>(r#"{"range":["1s"]}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid length 1, expected expect a tuple of 2 durations"),
+            "{err}"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_duration_bounded() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Config {
+            #[serde(
+                deserialize_with = "deserialize_duration_bounded::<100_000_000, 300_000_000_000, _, _>"
+            )]
+            timeout: Duration,
+            #[serde(
+                default,
+                deserialize_with = "deserialize_duration_bounded::<0, 1_000_000_000, _, _>"
+            )]
+            delay: Option<Duration>,
+        }
+
+        let json = r#"{"timeout":"5m","delay":null}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.timeout, Duration::from_secs(300));
+        assert_eq!(config.delay, None);
+
+        let json = r#"{"timeout":"100ms","delay":"1s"}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.timeout, Duration::from_millis(100));
+        assert_eq!(config.delay, Some(Duration::from_secs(1)));
+
+        for (json, msg) in [
+            (
+                r#"{"timeout":"5m 1s"}"#,
+                "duration 5min 1s is out of range, expected between 100ms and 5min",
+            ),
+            (
+                r#"{"timeout":"99ms"}"#,
+                "duration 99ms is out of range, expected between 100ms and 5min",
+            ),
+            (
+                r#"{"timeout":1,"delay":"2s"}"#,
+                "duration 2s is out of range, expected between 0s and 1s",
+            ),
+        ] {
+            let err = serde_json::from_str::<Config>(json).unwrap_err();
+            assert!(err.to_string().starts_with(msg), "{err}");
+        }
+    }
+
+    #[cfg(all(feature = "chrono", feature = "serde"))]
+    #[test]
+    fn test_deserialize_duration_bounded_chrono() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "deserialize_duration_bounded::<0, 60_000_000_000, _, _>")]
+            offset: CDuration,
+        }
+
+        let config: Config = serde_json::from_str(r#"{"offset":"1m"}"#).unwrap();
+        assert_eq!(config.offset, CDuration::minutes(1));
+        let err = serde_json::from_str::<Config>(r#"{"offset":"-1s"}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("duration -1s is out of range, expected between 0s and 1min"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_bounded_duration() {
+        type Timeout = BoundedDuration<100_000_000, 300_000_000_000>;
+
+        assert_eq!(Timeout::MIN, Duration::from_millis(100));
+        assert_eq!(Timeout::MAX, Duration::from_secs(300));
+        assert_eq!(Timeout::new(Duration::from_secs(1)).unwrap().as_secs(), 1);
+        assert_eq!(Timeout::new(Duration::from_secs(301)), None);
+
+        let timeout: Timeout = serde_json::from_str("90").unwrap();
+        assert_eq!(Duration::from(timeout), Duration::from_secs(90));
+        assert_eq!(timeout.to_string(), "1min 30s");
+        assert_eq!(serde_json::to_string(&timeout).unwrap(), r#""1min 30s""#);
+        let err = serde_json::from_str::<Timeout>(r#""1ms""#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "duration 1ms is out of range, expected between 100ms and 5min"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_duration() {
+        #[derive(Debug, serde::Serialize)]
+        struct Config {
+            #[serde(serialize_with = "serialize_duration")]
+            time_ticker: std::time::Duration,
+            #[serde(serialize_with = "serialize_duration")]
+            timeout: Option<TDuration>,
+            #[serde(serialize_with = "serialize_duration")]
+            interval: Option<std::time::Duration>,
+        }
+
+        let config = Config {
+            time_ticker: std::time::Duration::new(5400, 500_000_000),
+            timeout: Some(TDuration::seconds(-90)),
+            interval: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"time_ticker":"1h 30min 500ms","timeout":"-1min -30s","interval":null}"#
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "jiff"))]
+    #[test]
+    fn test_serde_jiff() {
+        use jiff::ToSpan;
+
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(with = "crate::serde_human")]
+            period: jiff::Span,
+            #[serde(with = "crate::serde_human")]
+            timeout: jiff::SignedDuration,
+            #[serde(default, with = "crate::serde_human")]
+            retry: Option<jiff::SignedDuration>,
+        }
+
+        let json = r#"{"period":"1mon 1.5d","timeout":"-90s","retry":null}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.period.fieldwise(), 1.month().days(1).hours(12));
+        assert_eq!(config.timeout, jiff::SignedDuration::from_secs(-90));
+        assert_eq!(config.retry, None);
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"period":"1mon 1d 12h","timeout":"-1min -30s","retry":null}"#
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "chrono"))]
+    #[test]
+    fn test_serde_human() {
+        #[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+        struct Config {
+            #[serde(with = "crate::serde_human")]
+            time_ticker: std::time::Duration,
+            #[serde(with = "crate::serde_human")]
+            chrono: chrono::Duration,
+            #[serde(with = "crate::serde_human")]
+            time: TDuration,
+            #[serde(default, with = "crate::serde_human")]
+            timeout: Option<std::time::Duration>,
+        }
+
+        let config = Config {
+            time_ticker: std::time::Duration::new(400 * 86400 + 1, 2),
+            chrono: chrono::Duration::milliseconds(-1500),
+            time: TDuration::ZERO,
+            timeout: Some(std::time::Duration::from_micros(7)),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            r#"{"time_ticker":"1y 1mon 5d 1s 2ns","chrono":"-1s -500ms","time":"0s","timeout":"7µs"}"#
+        );
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+
+        let json = r#"{"time_ticker":"1h","chrono":"1m","time":"1s"}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.timeout, None);
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+
+        // the longest duration that parses back, and the first that does not
+        let mut config = config;
+        config.time_ticker = std::time::Duration::from_nanos(u64::MAX);
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+        config.time_ticker += std::time::Duration::from_nanos(1);
+        let err = serde_json::to_string(&config).unwrap_err();
+        assert_eq!(err.to_string(), "overflow error");
+        config.time_ticker = std::time::Duration::MAX;
+        assert!(serde_json::to_string(&config).is_err());
+    }
+
     // Test backward compatibility with deprecated functions
     #[cfg(feature = "serde")]
     #[test]
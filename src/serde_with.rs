@@ -0,0 +1,140 @@
+use crate::{DefaultUnit, DeserializeDuration, DurationParser, Seconds, SerializeDuration};
+use std::marker::PhantomData;
+
+/// A [`serde_with`](https://docs.rs/serde_with) adapter for duration strings, for
+/// `#[serde_as(as = "DurationStr")]`.
+///
+/// It works for every type [`DeserializeDuration`] and [`SerializeDuration`] support and
+/// composes with `serde_with`'s own adapters, e.g. `Vec<DurationStr>`,
+/// `Option<DurationStr>` or `HashMap<_, DurationStr>`. Bare numbers are read in the unit `U`,
+/// seconds by default: `DurationStr<Millis>` reads `"500"` as 500ms.
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::DurationStr;
+/// use serde::{Deserialize, Serialize};
+/// use serde_with::serde_as;
+/// use std::time::Duration;
+///
+/// #[serde_as]
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde_as(as = "Vec<DurationStr>")]
+///     backoff: Vec<Duration>,
+///     #[serde_as(as = "Option<DurationStr>")]
+///     timeout: Option<Duration>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{"backoff":["1s","1m"],"timeout":null}"#).unwrap();
+/// assert_eq!(config.backoff, [Duration::new(1, 0), Duration::new(60, 0)]);
+/// assert_eq!(
+///     serde_json::to_string(&config).unwrap(),
+///     r#"{"backoff":["1s","1min"],"timeout":null}"#
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DurationStr<U = Seconds>(PhantomData<U>);
+
+impl<'de, T, U> ::serde_with::DeserializeAs<'de, T> for DurationStr<U>
+where
+    T: DeserializeDuration<'de>,
+    U: DefaultUnit,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize_duration_with(deserializer, &DurationParser::new().default_unit(U::UNIT))
+    }
+}
+
+impl<T, U> ::serde_with::SerializeAs<T> for DurationStr<U>
+where
+    T: SerializeDuration,
+{
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        source.serialize_duration(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Millis;
+    use serde::{Deserialize, Serialize};
+    use serde_with::serde_as;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    #[test]
+    fn test_duration_str() {
+        #[serde_as]
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        struct Config {
+            #[serde_as(as = "DurationStr")]
+            timeout: Duration,
+            #[serde_as(as = "Vec<DurationStr>")]
+            backoff: Vec<Duration>,
+            #[serde_as(as = "Option<DurationStr>")]
+            #[serde(default)]
+            retry: Option<Duration>,
+            #[serde_as(as = "BTreeMap<_, Option<Option<DurationStr>>>")]
+            endpoints: BTreeMap<String, Option<Option<Duration>>>,
+            #[serde_as(as = "DurationStr<Millis>")]
+            delay: Duration,
+        }
+
+        let json = r#"{
+            "timeout": "1m 30s",
+            "backoff": ["1s", 2],
+            "endpoints": {"a": "5m", "b": null},
+            "delay": "250"
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                timeout: Duration::new(90, 0),
+                backoff: vec![Duration::new(1, 0), Duration::new(2, 0)],
+                retry: None,
+                endpoints: BTreeMap::from([
+                    ("a".to_string(), Some(Some(Duration::new(300, 0)))),
+                    ("b".to_string(), None),
+                ]),
+                delay: Duration::from_millis(250),
+            }
+        );
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            r#"{"timeout":"1min 30s","backoff":["1s","2s"],"retry":null,"endpoints":{"a":"5min","b":null},"delay":"250ms"}"#
+        );
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_duration_str_chrono() {
+        #[serde_as]
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        struct Config {
+            #[serde_as(as = "[DurationStr; 2]")]
+            range: [chrono::Duration; 2],
+        }
+
+        let config: Config = serde_json::from_str(r#"{"range":["-1h","1h"]}"#).unwrap();
+        assert_eq!(
+            config.range,
+            [chrono::Duration::hours(-1), chrono::Duration::hours(1)]
+        );
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"range":["-1h","1h"]}"#
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{DError, ONE_SECOND_NANOSECOND};
+use std::ops::Neg;
+use std::time::Duration;
+
+/// A signed duration with nanosecond precision, as returned by [`parse_signed`](crate::parse_signed).
+///
+/// Unlike `std::time::Duration` it can hold negative values such as `-5m` or `1h - 2h`.
+/// The value is stored as an `i128` count of nanoseconds, so conversions into
+/// `chrono::Duration` and `time::Duration` never lose precision; they only fail when the
+/// value is out of the target type's range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignedDuration {
+    nanos: i128,
+}
+
+impl SignedDuration {
+    /// A duration of zero length.
+    pub const ZERO: SignedDuration = SignedDuration { nanos: 0 };
+
+    /// Create a `SignedDuration` from a count of nanoseconds.
+    pub const fn from_nanos(nanos: i128) -> Self {
+        SignedDuration { nanos }
+    }
+
+    /// Total number of nanoseconds.
+    pub const fn as_nanos(&self) -> i128 {
+        self.nanos
+    }
+
+    /// Whole seconds, truncated towards zero.
+    pub const fn as_secs(&self) -> i128 {
+        self.nanos / ONE_SECOND_NANOSECOND as i128
+    }
+
+    /// Fractional part in nanoseconds, carrying the same sign as the duration.
+    pub const fn subsec_nanos(&self) -> i32 {
+        (self.nanos % ONE_SECOND_NANOSECOND as i128) as i32
+    }
+
+    /// Returns `true` if the duration is strictly less than zero.
+    pub const fn is_negative(&self) -> bool {
+        self.nanos < 0
+    }
+
+    /// The absolute value as a `std::time::Duration`, or `None` if it does not fit.
+    pub fn unsigned_abs(&self) -> Option<Duration> {
+        to_std(self.nanos.unsigned_abs()).ok()
+    }
+}
+
+fn to_std(nanos: u128) -> Result<Duration, DError> {
+    let secs =
+        u64::try_from(nanos / ONE_SECOND_NANOSECOND as u128).map_err(|_| DError::OverflowError)?;
+    let nanos = (nanos % ONE_SECOND_NANOSECOND as u128) as u32;
+    Ok(Duration::new(secs, nanos))
+}
+
+impl Neg for SignedDuration {
+    type Output = SignedDuration;
+
+    fn neg(self) -> Self::Output {
+        SignedDuration { nanos: -self.nanos }
+    }
+}
+
+impl From<Duration> for SignedDuration {
+    fn from(duration: Duration) -> Self {
+        SignedDuration::from_nanos(duration.as_nanos() as i128)
+    }
+}
+
+impl TryFrom<SignedDuration> for Duration {
+    type Error = DError;
+
+    fn try_from(duration: SignedDuration) -> Result<Self, Self::Error> {
+        if duration.is_negative() {
+            return Err(DError::NegativeError);
+        }
+        to_std(duration.nanos.unsigned_abs())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::Duration> for SignedDuration {
+    fn from(duration: chrono::Duration) -> Self {
+        SignedDuration::from_nanos(
+            duration.num_seconds() as i128 * ONE_SECOND_NANOSECOND as i128
+                + duration.subsec_nanos() as i128,
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<SignedDuration> for chrono::Duration {
+    type Error = DError;
+
+    fn try_from(duration: SignedDuration) -> Result<Self, Self::Error> {
+        let secs = duration.nanos.div_euclid(ONE_SECOND_NANOSECOND as i128);
+        let nanos = duration.nanos.rem_euclid(ONE_SECOND_NANOSECOND as i128);
+        let secs = i64::try_from(secs).map_err(|_| DError::OverflowError)?;
+        chrono::Duration::new(secs, nanos as u32).ok_or(DError::OverflowError)
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::Duration> for SignedDuration {
+    fn from(duration: time::Duration) -> Self {
+        SignedDuration::from_nanos(duration.whole_nanoseconds())
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<SignedDuration> for time::Duration {
+    type Error = DError;
+
+    fn try_from(duration: SignedDuration) -> Result<Self, Self::Error> {
+        let secs = i64::try_from(duration.as_secs()).map_err(|_| DError::OverflowError)?;
+        Ok(time::Duration::new(secs, duration.subsec_nanos()))
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl From<jiff::SignedDuration> for SignedDuration {
+    fn from(duration: jiff::SignedDuration) -> Self {
+        SignedDuration::from_nanos(duration.as_nanos())
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl TryFrom<SignedDuration> for jiff::SignedDuration {
+    type Error = DError;
+
+    fn try_from(duration: SignedDuration) -> Result<Self, Self::Error> {
+        jiff::SignedDuration::try_from_nanos_i128(duration.nanos).ok_or(DError::OverflowError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_signed;
+
+    #[test]
+    fn test_signed_duration() {
+        let duration = SignedDuration::from_nanos(-1_500_000_000);
+        assert!(duration.is_negative());
+        assert_eq!(duration.as_secs(), -1);
+        assert_eq!(duration.subsec_nanos(), -500_000_000);
+        assert_eq!(duration.unsigned_abs(), Some(Duration::new(1, 500_000_000)));
+        assert_eq!(-duration, SignedDuration::from_nanos(1_500_000_000));
+    }
+
+    #[test]
+    fn test_signed_duration_to_std() {
+        let duration = parse_signed("1h").unwrap();
+        assert_eq!(Duration::try_from(duration), Ok(Duration::new(3600, 0)));
+
+        let duration = parse_signed("-1h").unwrap();
+        assert_eq!(Duration::try_from(duration), Err(DError::NegativeError));
+
+        let duration = SignedDuration::from_nanos(i128::MAX);
+        assert_eq!(Duration::try_from(duration), Err(DError::OverflowError));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_signed_duration_chrono() {
+        let duration = parse_signed("-1.5s").unwrap();
+        let chrono_duration = chrono::Duration::try_from(duration).unwrap();
+        assert_eq!(chrono_duration, chrono::Duration::milliseconds(-1500));
+        assert_eq!(SignedDuration::from(chrono_duration), duration);
+
+        let duration = parse_signed("-1ns").unwrap();
+        let chrono_duration = chrono::Duration::try_from(duration).unwrap();
+        assert_eq!(chrono_duration, chrono::Duration::nanoseconds(-1));
+        assert_eq!(SignedDuration::from(chrono_duration), duration);
+
+        let duration = SignedDuration::from_nanos(i128::MIN);
+        assert_eq!(
+            chrono::Duration::try_from(duration),
+            Err(DError::OverflowError)
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_signed_duration_time() {
+        let duration = parse_signed("-1.5s").unwrap();
+        let time_duration = time::Duration::try_from(duration).unwrap();
+        assert_eq!(time_duration, time::Duration::milliseconds(-1500));
+        assert_eq!(SignedDuration::from(time_duration), duration);
+
+        let duration = SignedDuration::from_nanos(i128::MIN);
+        assert_eq!(
+            time::Duration::try_from(duration),
+            Err(DError::OverflowError)
+        );
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_signed_duration_jiff() {
+        let duration = parse_signed("-1.5s").unwrap();
+        let jiff_duration = jiff::SignedDuration::try_from(duration).unwrap();
+        assert_eq!(jiff_duration, jiff::SignedDuration::from_millis(-1500));
+        assert_eq!(SignedDuration::from(jiff_duration), duration);
+
+        let duration = SignedDuration::from_nanos(i128::MIN);
+        assert_eq!(
+            jiff::SignedDuration::try_from(duration),
+            Err(DError::OverflowError)
+        );
+    }
+}
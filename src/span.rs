@@ -0,0 +1,486 @@
+use crate::unit::TimeUnit;
+use crate::{round_nanos, DError, DResult, DurationParser, SignedDuration, ONE_DAY_NANOSECOND};
+#[cfg(feature = "jiff")]
+use crate::{
+    ONE_HOUR_NANOSECOND, ONE_MICROSECOND_NANOSECOND, ONE_MILLISECOND_NANOSECOND,
+    ONE_MINUTE_NANOSECOND, ONE_SECOND_NANOSECOND,
+};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::ops::Neg;
+
+const CALENDAR_UNITS: [TimeUnit; 4] = [
+    TimeUnit::Year,
+    TimeUnit::Month,
+    TimeUnit::Week,
+    TimeUnit::Day,
+];
+
+/// Exact amounts of the calendar units and of the remaining time while a span is evaluated.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct SpanValue {
+    /// Years, months, weeks and days.
+    calendar: [Decimal; 4],
+    /// Everything else, in nanoseconds.
+    nanos: Decimal,
+}
+
+impl SpanValue {
+    pub(crate) fn from_nanos(nanos: Decimal) -> Self {
+        SpanValue {
+            calendar: Default::default(),
+            nanos,
+        }
+    }
+
+    pub(crate) fn from_quantity(
+        unit: TimeUnit,
+        quantity: Decimal,
+        parser: &DurationParser,
+    ) -> DResult<Self> {
+        let mut value = SpanValue::default();
+        match CALENDAR_UNITS.iter().position(|calendar| *calendar == unit) {
+            Some(index) => value.calendar[index] = quantity,
+            None => {
+                value.nanos = quantity
+                    .checked_mul(parser.unit_nanos(unit))
+                    .ok_or(DError::OverflowError)?
+            }
+        }
+        Ok(value)
+    }
+
+    /// Whether no calendar unit is set.
+    pub(crate) fn is_time(&self) -> bool {
+        self.calendar.iter().all(Decimal::is_zero)
+    }
+
+    pub(crate) fn checked_add(&self, rhs: &SpanValue) -> DResult<SpanValue> {
+        let mut value = *self;
+        for (lhs, rhs) in value.calendar.iter_mut().zip(rhs.calendar) {
+            *lhs = lhs.checked_add(rhs).ok_or(DError::OverflowError)?;
+        }
+        value.nanos = value
+            .nanos
+            .checked_add(rhs.nanos)
+            .ok_or(DError::OverflowError)?;
+        Ok(value)
+    }
+
+    pub(crate) fn checked_mul(&self, factor: Decimal) -> DResult<SpanValue> {
+        let mut value = *self;
+        for quantity in value.calendar.iter_mut().chain([&mut value.nanos]) {
+            *quantity = quantity.checked_mul(factor).ok_or(DError::OverflowError)?;
+        }
+        Ok(value)
+    }
+
+    /// The whole span in nanoseconds, with the unit lengths of `parser`.
+    pub(crate) fn flatten(&self, parser: &DurationParser) -> DResult<Decimal> {
+        CALENDAR_UNITS
+            .iter()
+            .zip(self.calendar)
+            .try_fold(self.nanos, |acc, (unit, quantity)| {
+                quantity
+                    .checked_mul(parser.unit_nanos(*unit))
+                    .and_then(|nanos| acc.checked_add(nanos))
+            })
+            .ok_or(DError::OverflowError)
+    }
+
+    /// Round to whole calendar units and nanoseconds. A fraction of a unit is carried into the
+    /// next smaller one, so `1.5y` is one year and six months and `1.5d` one day and twelve
+    /// hours; a month has as many days as `parser`'s `month_length`.
+    pub(crate) fn round(&self, parser: &DurationParser) -> DResult<Span> {
+        fn split(quantity: Decimal) -> DResult<(i64, Decimal)> {
+            let whole = quantity.trunc();
+            let whole_i64 = whole.to_i64().ok_or(DError::OverflowError)?;
+            Ok((whole_i64, quantity - whole))
+        }
+        fn carry(quantity: Decimal, fraction: Decimal, per_unit: Decimal) -> DResult<Decimal> {
+            fraction
+                .checked_mul(per_unit)
+                .and_then(|carried| quantity.checked_add(carried))
+                .ok_or(DError::OverflowError)
+        }
+
+        let [years, months, weeks, days] = self.calendar;
+        let day = Decimal::from(ONE_DAY_NANOSECOND);
+        let (years, fraction) = split(years)?;
+        let (months, fraction) = split(carry(months, fraction, 12.into())?)?;
+        let days = carry(days, fraction, parser.unit_nanos(TimeUnit::Month) / day)?;
+        let (weeks, fraction) = split(weeks)?;
+        let (days, fraction) = split(carry(days, fraction, 7.into())?)?;
+        let nanos = round_nanos(carry(self.nanos, fraction, day)?)?;
+        Ok(Span {
+            years,
+            months,
+            weeks,
+            days,
+            nanos,
+        })
+    }
+}
+
+impl Neg for SpanValue {
+    type Output = SpanValue;
+
+    fn neg(self) -> SpanValue {
+        SpanValue {
+            calendar: self.calendar.map(Neg::neg),
+            nanos: -self.nanos,
+        }
+    }
+}
+
+/// A duration with its years, months, weeks and days kept apart from the time, see
+/// [`parse_span`](crate::parse_span).
+///
+/// Calendar units have no fixed length, so a span only becomes a point in time once it is added
+/// to one with [`Span::apply_to`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub(crate) years: i64,
+    pub(crate) months: i64,
+    pub(crate) weeks: i64,
+    pub(crate) days: i64,
+    /// Everything else, in nanoseconds.
+    pub(crate) nanos: i128,
+}
+
+impl Span {
+    /// The number of years.
+    pub const fn years(&self) -> i64 {
+        self.years
+    }
+
+    /// The number of months, not counting the years.
+    pub const fn months(&self) -> i64 {
+        self.months
+    }
+
+    /// The number of weeks.
+    pub const fn weeks(&self) -> i64 {
+        self.weeks
+    }
+
+    /// The number of days, not counting the weeks.
+    pub const fn days(&self) -> i64 {
+        self.days
+    }
+
+    /// The exact part below the calendar units, e.g. 25 hours for `1d 25h`.
+    pub const fn time(&self) -> SignedDuration {
+        SignedDuration::from_nanos(self.nanos)
+    }
+
+    /// Add this span to `anchor`, or `None` if the result is out of range.
+    ///
+    /// Years and months are added first; if the day does not exist in the resulting month it is
+    /// clamped to the last one, so one month after January 31st is the end of February. Weeks and
+    /// days follow, then the exact time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "chrono")]
+    /// # {
+    /// use chrono::NaiveDate;
+    /// use duration_str::parse_span;
+    ///
+    /// let anchor = NaiveDate::from_ymd_opt(2024, 1, 31)
+    ///     .unwrap()
+    ///     .and_hms_opt(12, 0, 0)
+    ///     .unwrap();
+    /// let span = parse_span("1mon 1d 2h").unwrap();
+    /// let expected = NaiveDate::from_ymd_opt(2024, 3, 1)
+    ///     .unwrap()
+    ///     .and_hms_opt(14, 0, 0)
+    ///     .unwrap();
+    /// assert_eq!(span.apply_to(anchor), Some(expected));
+    /// # }
+    /// ```
+    pub fn apply_to<T: SpanAnchor>(&self, anchor: T) -> Option<T> {
+        anchor.checked_add_span(self)
+    }
+
+    /// Years and months as a number of months.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    fn total_months(&self) -> Option<i64> {
+        self.years
+            .checked_mul(12)
+            .and_then(|months| months.checked_add(self.months))
+    }
+
+    /// Weeks and days as a number of days.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    fn total_days(&self) -> Option<i64> {
+        self.weeks
+            .checked_mul(7)
+            .and_then(|days| days.checked_add(self.days))
+    }
+}
+
+impl Neg for Span {
+    type Output = Span;
+
+    fn neg(self) -> Span {
+        Span {
+            years: -self.years,
+            months: -self.months,
+            weeks: -self.weeks,
+            days: -self.days,
+            nanos: -self.nanos,
+        }
+    }
+}
+
+/// A date and time that a [`Span`] can be added to, see [`Span::apply_to`].
+pub trait SpanAnchor: Sized {
+    /// Add `span` in calendar order, or return `None` if the result is out of range.
+    fn checked_add_span(self, span: &Span) -> Option<Self>;
+}
+
+#[cfg(feature = "chrono")]
+impl SpanAnchor for chrono::NaiveDateTime {
+    fn checked_add_span(self, span: &Span) -> Option<Self> {
+        use chrono::{Days, Months};
+
+        let months = span.total_months()?;
+        let months_abs = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+        let date_time = if months < 0 {
+            self.checked_sub_months(months_abs)?
+        } else {
+            self.checked_add_months(months_abs)?
+        };
+        let days = span.total_days()?;
+        let date_time = if days < 0 {
+            date_time.checked_sub_days(Days::new(days.unsigned_abs()))?
+        } else {
+            date_time.checked_add_days(Days::new(days.unsigned_abs()))?
+        };
+        date_time.checked_add_signed(chrono::Duration::try_from(span.time()).ok()?)
+    }
+}
+
+#[cfg(feature = "time")]
+impl SpanAnchor for time::OffsetDateTime {
+    fn checked_add_span(self, span: &Span) -> Option<Self> {
+        let date = self.date();
+        let month = i64::from(date.year()) * 12 + date.month() as i64 - 1;
+        let month = month.checked_add(span.total_months()?)?;
+        let year = i32::try_from(month.div_euclid(12)).ok()?;
+        let month = time::Month::try_from(month.rem_euclid(12) as u8 + 1).ok()?;
+        let day = date.day().min(month.length(year));
+        let date = time::Date::from_calendar_date(year, month, day).ok()?;
+
+        let days = span.total_days()?;
+        let date = date.checked_add(time::Duration::seconds(days.checked_mul(86400)?))?;
+        self.replace_date(date)
+            .checked_add(time::Duration::try_from(span.time()).ok()?)
+    }
+}
+
+/// The time is split into hours down to nanoseconds, e.g. `25h` stays 25 hours. jiff spans
+/// have a single sign, so `1mon - 1d` is an error.
+#[cfg(feature = "jiff")]
+impl TryFrom<Span> for jiff::Span {
+    type Error = DError;
+
+    fn try_from(span: Span) -> Result<Self, Self::Error> {
+        let signs = [
+            span.years.signum(),
+            span.months.signum(),
+            span.weeks.signum(),
+            span.days.signum(),
+            span.nanos.signum() as i64,
+        ];
+        if signs.contains(&1) && signs.contains(&-1) {
+            return Err(DError::ParseError(
+                "jiff::Span does not support units with mixed signs".to_string(),
+            ));
+        }
+
+        let (hours, rest) = div_rem(span.nanos, ONE_HOUR_NANOSECOND);
+        let (minutes, rest) = div_rem(rest, ONE_MINUTE_NANOSECOND);
+        let (seconds, rest) = div_rem(rest, ONE_SECOND_NANOSECOND);
+        let (millis, rest) = div_rem(rest, ONE_MILLISECOND_NANOSECOND);
+        let (micros, nanos) = div_rem(rest, ONE_MICROSECOND_NANOSECOND);
+        let hours = i64::try_from(hours).map_err(|_| DError::OverflowError)?;
+        jiff::Span::new()
+            .try_years(span.years)
+            .and_then(|s| s.try_months(span.months))
+            .and_then(|s| s.try_weeks(span.weeks))
+            .and_then(|s| s.try_days(span.days))
+            .and_then(|s| s.try_hours(hours))
+            .and_then(|s| s.try_minutes(minutes as i64))
+            .and_then(|s| s.try_seconds(seconds as i64))
+            .and_then(|s| s.try_milliseconds(millis as i64))
+            .and_then(|s| s.try_microseconds(micros as i64))
+            .and_then(|s| s.try_nanoseconds(nanos as i64))
+            .map_err(|_| DError::OverflowError)
+    }
+}
+
+#[cfg(feature = "jiff")]
+fn div_rem(nanos: i128, unit: u64) -> (i128, i128) {
+    (nanos / unit as i128, nanos % unit as i128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorKind, ONE_HOUR_NANOSECOND};
+
+    fn span(input: &str) -> Result<Span, ErrorKind> {
+        crate::parse_span(input).map_err(|err| err.kind())
+    }
+
+    fn calendar(years: i64, months: i64, weeks: i64, days: i64, hours: i128) -> Span {
+        Span {
+            years,
+            months,
+            weeks,
+            days,
+            nanos: hours * ONE_HOUR_NANOSECOND as i128,
+        }
+    }
+
+    #[test]
+    fn test_span() {
+        assert_eq!(span("1y 2mon 3w 4d 5h"), Ok(calendar(1, 2, 3, 4, 5)));
+        assert_eq!(span("25h"), Ok(calendar(0, 0, 0, 0, 25)));
+        assert_eq!(span("1.5y"), Ok(calendar(1, 6, 0, 0, 0)));
+        assert_eq!(span("1.5w"), Ok(calendar(0, 0, 1, 3, 12)));
+        assert_eq!(span("1.5mon"), Ok(calendar(0, 1, 0, 15, 0)));
+        assert_eq!(span("P1Y2M3W4DT5H"), Ok(calendar(1, 2, 3, 4, 5)));
+        assert_eq!(span("-P1DT1H"), Ok(calendar(0, 0, 0, -1, -1)));
+        assert_eq!(span("02:00:00"), Ok(calendar(0, 0, 0, 0, 2)));
+        assert_eq!(span("1x"), Err(ErrorKind::InvalidUnit));
+        assert_eq!(span(""), Err(ErrorKind::Empty));
+    }
+
+    #[cfg(not(feature = "no_calc"))]
+    #[test]
+    fn test_span_expr() {
+        assert_eq!(span("1mon - 1d"), Ok(calendar(0, 1, 0, -1, 0)));
+        assert_eq!(span("(1y + 1d) * 2"), Ok(calendar(2, 0, 0, 2, 0)));
+        assert_eq!(span("2 * 1mon"), Ok(calendar(0, 2, 0, 0, 0)));
+        assert_eq!(span("1y / 4"), Ok(calendar(0, 3, 0, 0, 0)));
+        assert_eq!(span("-(1d 1h)"), Ok(calendar(0, 0, 0, -1, -1)));
+        assert_eq!(span("1d / 0"), Err(ErrorKind::DivisionByZero));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_apply_to_chrono() {
+        use chrono::{NaiveDate, NaiveDateTime};
+
+        fn at(y: i32, m: u32, d: u32, h: u32) -> NaiveDateTime {
+            NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        }
+        let apply = |input: &str, anchor| span(input).unwrap().apply_to(anchor);
+
+        assert_eq!(apply("1mon", at(2024, 1, 31, 0)), Some(at(2024, 2, 29, 0)));
+        assert_eq!(apply("1mon", at(2023, 1, 31, 0)), Some(at(2023, 2, 28, 0)));
+        assert_eq!(apply("1y", at(2024, 2, 29, 0)), Some(at(2025, 2, 28, 0)));
+        assert_eq!(apply("2mon", at(2024, 12, 31, 0)), Some(at(2025, 2, 28, 0)));
+        assert_eq!(
+            apply("1w 1d 25h", at(2024, 3, 1, 0)),
+            Some(at(2024, 3, 10, 1))
+        );
+        assert_eq!(
+            apply("-1mon", at(2024, 3, 31, 12)),
+            Some(at(2024, 2, 29, 12))
+        );
+        assert_eq!(
+            apply("-P1Y1DT1H", at(2024, 3, 1, 0)),
+            Some(at(2023, 2, 27, 23))
+        );
+        assert_eq!(apply("1.5mon", at(2024, 1, 1, 0)), Some(at(2024, 2, 16, 0)));
+        assert_eq!(apply("300000y", at(2024, 1, 1, 0)), None);
+        assert_eq!((-span("1mon 1d").unwrap()).months(), -1);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_apply_to_time() {
+        use time::{Date, Month, OffsetDateTime, UtcOffset};
+
+        fn at(y: i32, m: Month, d: u8, h: u8) -> OffsetDateTime {
+            Date::from_calendar_date(y, m, d)
+                .unwrap()
+                .with_hms(h, 0, 0)
+                .unwrap()
+                .assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap())
+        }
+        let apply = |input: &str, anchor| span(input).unwrap().apply_to(anchor);
+
+        use Month::*;
+        assert_eq!(
+            apply("1mon", at(2024, January, 31, 0)),
+            Some(at(2024, February, 29, 0))
+        );
+        assert_eq!(
+            apply("1y", at(2024, February, 29, 0)),
+            Some(at(2025, February, 28, 0))
+        );
+        assert_eq!(
+            apply("13mon", at(2024, December, 31, 0)),
+            Some(at(2026, January, 31, 0))
+        );
+        assert_eq!(
+            apply("-2mon", at(2024, January, 31, 3)),
+            Some(at(2023, November, 30, 3))
+        );
+        assert_eq!(
+            apply("1d -1h", at(2024, March, 1, 0)),
+            Some(at(2024, March, 1, 23))
+        );
+        assert_eq!(apply("300000y", at(2024, January, 1, 0)), None);
+        assert_eq!(
+            apply("1mon", at(2024, January, 31, 0)).unwrap().offset(),
+            UtcOffset::from_hms(2, 0, 0).unwrap()
+        );
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_jiff_span() {
+        use jiff::ToSpan;
+
+        let span = jiff::Span::try_from(calendar(1, 2, 3, 4, 25)).unwrap();
+        assert_eq!(
+            span.fieldwise(),
+            1.year().months(2).weeks(3).days(4).hours(25)
+        );
+
+        let time = Span {
+            nanos: -(90 * ONE_MINUTE_NANOSECOND as i128 + 1_002_003),
+            ..Span::default()
+        };
+        let span = jiff::Span::try_from(time).unwrap();
+        assert_eq!(
+            span.fieldwise(),
+            (-1).hour()
+                .minutes(-30)
+                .milliseconds(-1)
+                .microseconds(-2)
+                .nanoseconds(-3)
+        );
+
+        let mixed = calendar(0, 1, 0, -1, 0);
+        assert!(matches!(
+            jiff::Span::try_from(mixed),
+            Err(DError::ParseError(_))
+        ));
+        let years = calendar(20_000, 0, 0, 0, 0);
+        assert!(matches!(
+            jiff::Span::try_from(years),
+            Err(DError::OverflowError)
+        ));
+    }
+}
@@ -0,0 +1,315 @@
+use crate::error::token_len;
+use crate::parser::nanos_to_std;
+use crate::unit::TimeUnit;
+use crate::{DError, DurationError, ErrorKind, ONE_SECOND_NANOSECOND};
+use std::time::Duration;
+
+/// systemd's `USEC_PER_MONTH`: 30.44 days.
+pub(crate) const SYSTEMD_MONTH_NANOSECOND: u64 = 2_629_800 * ONE_SECOND_NANOSECOND;
+/// systemd's `USEC_PER_YEAR`: 365.25 days.
+pub(crate) const SYSTEMD_YEAR_NANOSECOND: u64 = 31_557_600 * ONE_SECOND_NANOSECOND;
+
+/// Unit suffixes in the order systemd's `extract_multiplier` tries them; the first prefix match
+/// wins, so longer suffixes come before their prefixes.
+const SYSTEMD_UNITS: [(&str, TimeUnit); 32] = [
+    ("seconds", TimeUnit::Second),
+    ("second", TimeUnit::Second),
+    ("sec", TimeUnit::Second),
+    ("s", TimeUnit::Second),
+    ("minutes", TimeUnit::Minute),
+    ("minute", TimeUnit::Minute),
+    ("min", TimeUnit::Minute),
+    ("months", TimeUnit::Month),
+    ("month", TimeUnit::Month),
+    ("M", TimeUnit::Month),
+    ("msec", TimeUnit::MilliSecond),
+    ("ms", TimeUnit::MilliSecond),
+    ("m", TimeUnit::Minute),
+    ("hours", TimeUnit::Hour),
+    ("hour", TimeUnit::Hour),
+    ("hr", TimeUnit::Hour),
+    ("h", TimeUnit::Hour),
+    ("days", TimeUnit::Day),
+    ("day", TimeUnit::Day),
+    ("d", TimeUnit::Day),
+    ("weeks", TimeUnit::Week),
+    ("week", TimeUnit::Week),
+    ("w", TimeUnit::Week),
+    ("years", TimeUnit::Year),
+    ("year", TimeUnit::Year),
+    ("y", TimeUnit::Year),
+    ("usec", TimeUnit::MicroSecond),
+    ("us", TimeUnit::MicroSecond),
+    ("μs", TimeUnit::MicroSecond), // U+03BC Greek small letter mu
+    ("µs", TimeUnit::MicroSecond), // U+00B5 micro sign
+    ("nsec", TimeUnit::NanoSecond),
+    ("ns", TimeUnit::NanoSecond),
+];
+
+/// Length of `unit` in nanoseconds, with systemd's month and year.
+pub(crate) fn systemd_nanos(unit: TimeUnit) -> u64 {
+    match unit {
+        TimeUnit::Year => SYSTEMD_YEAR_NANOSECOND,
+        TimeUnit::Month => SYSTEMD_MONTH_NANOSECOND,
+        unit => unit.nanos_per_unit(),
+    }
+}
+
+/// Strip the first matching unit suffix from `s`, like systemd's `extract_multiplier`.
+fn extract_multiplier(s: &str) -> Option<(u64, &str)> {
+    SYSTEMD_UNITS.iter().find_map(|(suffix, unit)| {
+        s.strip_prefix(suffix)
+            .map(|rest| (systemd_nanos(*unit), rest))
+    })
+}
+
+/// parse a systemd time span such as `1h 30min`, `2 weeks` or `1.5s`, like systemd's `parse_time`
+///
+/// The unit table is systemd's: `usec`/`us`/`µs`, `msec`/`ms`, `s`/`sec`/`second(s)`,
+/// `m`/`min`/`minute(s)`, `h`/`hr`/`hour(s)`, `d`/`day(s)`, `w`/`week(s)`, `M`/`month(s)`,
+/// `y`/`year(s)` and `nsec`/`ns`. A month is 30.44 days and a year 365.25 days, unlike
+/// [`parse`](crate::parse). Numbers without a unit are read in `default_unit`, which depends on
+/// the setting: most systemd settings use seconds, some use milliseconds or microseconds.
+/// `infinity` maps to `Duration::MAX`.
+///
+/// # Example
+///
+/// ```rust
+/// use duration_str::{parse_systemd, TimeUnit};
+/// use std::time::Duration;
+///
+/// let duration = parse_systemd("1h 30min", TimeUnit::Second).unwrap();
+/// assert_eq!(duration, Duration::new(5400, 0));
+///
+/// let duration = parse_systemd("1y", TimeUnit::Second).unwrap();
+/// assert_eq!(duration, Duration::new(31_557_600, 0));
+///
+/// let duration = parse_systemd("500", TimeUnit::MilliSecond).unwrap();
+/// assert_eq!(duration, Duration::from_millis(500));
+/// ```
+pub fn parse_systemd(
+    input: impl AsRef<str>,
+    default_unit: TimeUnit,
+) -> Result<Duration, DurationError> {
+    let input = input.as_ref();
+    let error = |kind: ErrorKind, rest: &str, len: usize| {
+        let start = input.len() - rest.len();
+        let expected = match kind {
+            ErrorKind::InvalidUnit => SYSTEMD_UNITS
+                .iter()
+                .map(|(suffix, _)| suffix.to_string())
+                .collect(),
+            _ => Vec::new(),
+        };
+        let message = match kind {
+            ErrorKind::Negative => DError::NegativeError.to_string(),
+            _ => format!("invalid systemd time span {:?}", input),
+        };
+        DurationError::new(kind, input, start..start + len, expected, message)
+    };
+    // a word where a number or unit belongs is a misspelled unit
+    let unexpected = |rest: &str| {
+        let kind = match rest.starts_with(char::is_alphabetic) {
+            true => ErrorKind::InvalidUnit,
+            false => ErrorKind::Syntax,
+        };
+        error(kind, rest, token_len(rest))
+    };
+
+    let mut p = input.trim_start();
+    if let Some(rest) = p.strip_prefix("infinity") {
+        return if rest.trim_start().is_empty() {
+            Ok(Duration::MAX)
+        } else {
+            Err(error(ErrorKind::Syntax, rest, token_len(rest)))
+        };
+    }
+
+    let mut total: u128 = 0;
+    let mut something = false;
+    loop {
+        p = p.trim_start();
+        if p.is_empty() {
+            if !something {
+                return Err(error(ErrorKind::Empty, p, 0));
+            }
+            break;
+        }
+        if let Some(term) = p.strip_prefix('-') {
+            return Err(error(ErrorKind::Negative, p, 1 + token_len(term)));
+        }
+
+        let number = p.strip_prefix('+').unwrap_or(p);
+        let int_len = number
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(number.len());
+        let (int, rest) = number.split_at(int_len);
+        let fraction = match rest.strip_prefix('.') {
+            Some(rest) => {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                Some(&rest[..len])
+            }
+            None if int.is_empty() => return Err(unexpected(number)),
+            None => None,
+        };
+        // Don't allow `3.sec` or `3. 1`
+        if fraction == Some("") {
+            return Err(error(ErrorKind::Syntax, rest, 1));
+        }
+        p = &rest[fraction.map_or(0, |fraction| fraction.len() + 1)..];
+
+        let (multiplier, rest) =
+            extract_multiplier(p.trim_start()).unwrap_or((systemd_nanos(default_unit), p));
+        // Don't allow `12.34.56`, but accept `12.34 .56` or `12.34s.56`
+        if rest.len() == p.len() && !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            return Err(unexpected(rest));
+        }
+        p = rest;
+
+        let overflow = || DurationError::from_error(input, DError::OverflowError);
+        let int: u128 = if int.is_empty() {
+            0
+        } else {
+            int.parse().map_err(|_| overflow())?
+        };
+        total = int
+            .checked_mul(multiplier.into())
+            .and_then(|nanos| total.checked_add(nanos))
+            .ok_or_else(overflow)?;
+        something = true;
+
+        if let Some(fraction) = fraction {
+            // each digit is truncated to the nanosecond, as systemd does to the microsecond
+            let mut m = u128::from(multiplier) / 10;
+            for digit in fraction.bytes() {
+                total = total
+                    .checked_add(u128::from(digit - b'0') * m)
+                    .ok_or_else(overflow)?;
+                m /= 10;
+            }
+        }
+    }
+
+    let nanos = i128::try_from(total)
+        .map_err(|_| DurationError::from_error(input, DError::OverflowError))?;
+    nanos_to_std(nanos).map_err(|err| DurationError::from_error(input, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catch_err;
+
+    #[test]
+    fn test_parse_systemd() {
+        let cases = [
+            ("1h 30min", Duration::new(5400, 0)),
+            ("1h30min", Duration::new(5400, 0)),
+            ("2 weeks", Duration::new(14 * 86400, 0)),
+            ("5 months", Duration::new(5 * 2_629_800, 0)),
+            ("1M", Duration::new(2_629_800, 0)),
+            ("1y", Duration::new(31_557_600, 0)),
+            ("1 year 1 month", Duration::new(31_557_600 + 2_629_800, 0)),
+            ("5m", Duration::new(300, 0)),
+            ("5ms", Duration::from_millis(5)),
+            ("5msec", Duration::from_millis(5)),
+            ("7usec", Duration::from_micros(7)),
+            ("7µs 8μs", Duration::from_micros(15)),
+            ("3ns", Duration::from_nanos(3)),
+            ("3 nsec", Duration::from_nanos(3)),
+            ("1.5s", Duration::from_millis(1500)),
+            (".5s", Duration::from_millis(500)),
+            ("0.5 hours", Duration::new(1800, 0)),
+            ("1.5", Duration::from_millis(1500)),
+            ("  2days 3hr  ", Duration::new(2 * 86400 + 3 * 3600, 0)),
+            ("12.34s.56", Duration::new(12, 900_000_000)),
+            ("12.34 .56", Duration::new(12, 900_000_000)),
+            ("+5s", Duration::new(5, 0)),
+            ("0", Duration::ZERO),
+            ("infinity", Duration::MAX),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                parse_systemd(input, TimeUnit::Second),
+                Ok(expected),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_systemd_default_unit() {
+        assert_eq!(
+            parse_systemd("500", TimeUnit::MilliSecond),
+            Ok(Duration::from_millis(500))
+        );
+        assert_eq!(
+            parse_systemd("500 2s", TimeUnit::MicroSecond),
+            Ok(Duration::new(2, 500_000))
+        );
+        assert_eq!(
+            parse_systemd("1", TimeUnit::Month),
+            Ok(Duration::new(2_629_800, 0))
+        );
+        assert_eq!(
+            parse_systemd("1.5", TimeUnit::Minute),
+            Ok(Duration::new(90, 0))
+        );
+    }
+
+    #[test]
+    fn test_parse_systemd_err() {
+        for input in [
+            "",
+            "   ",
+            "s",
+            "12.34.56",
+            "3.sec",
+            "3. 1",
+            "3.",
+            "1x",
+            "1 fortnight",
+            "infinityx",
+        ] {
+            assert_eq!(
+                catch_err!(parse_systemd(input, TimeUnit::Second)),
+                format!("invalid systemd time span {:?}", input),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            catch_err!(parse_systemd("-1s", TimeUnit::Second)),
+            "negative duration is not supported by std::time::Duration"
+        );
+        assert_eq!(
+            catch_err!(parse_systemd("1s -0", TimeUnit::Second)),
+            "negative duration is not supported by std::time::Duration"
+        );
+        assert_eq!(
+            catch_err!(parse_systemd("600000000000y", TimeUnit::Second)),
+            "overflow error"
+        );
+    }
+
+    #[test]
+    fn test_parse_systemd_err_kind() {
+        let cases = [
+            ("", ErrorKind::Empty, 0..0),
+            ("   ", ErrorKind::Empty, 3..3),
+            ("1x", ErrorKind::InvalidUnit, 1..2),
+            ("1 fortnight", ErrorKind::InvalidUnit, 2..11),
+            ("12.34.56", ErrorKind::Syntax, 5..8),
+            ("3.sec", ErrorKind::Syntax, 1..2),
+            ("infinityx", ErrorKind::Syntax, 8..9),
+            ("1s -0", ErrorKind::Negative, 3..5),
+            ("600000000000y", ErrorKind::Overflow, 0..13),
+        ];
+        for (input, kind, span) in cases {
+            let err = parse_systemd(input, TimeUnit::Second).unwrap_err();
+            assert_eq!(err.kind(), kind, "{input}");
+            assert_eq!(err.span(), span, "{input}");
+        }
+    }
+}
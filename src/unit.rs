@@ -1,21 +1,26 @@
 use crate::{
-    impl_expect_err, impl_expect_err_internal, CondUnit, DError, DResult, ExpectErr,
-    ONE_DAY_NANOSECOND, ONE_HOUR_NANOSECOND, ONE_MICROSECOND_NANOSECOND,
+    impl_expect_err, impl_expect_err_internal, CondUnit, DError, DResult, DurationParser,
+    ExpectErr, ONE_DAY_NANOSECOND, ONE_HOUR_NANOSECOND, ONE_MICROSECOND_NANOSECOND,
     ONE_MILLISECOND_NANOSECOND, ONE_MINUTE_NANOSECOND, ONE_MONTH_NANOSECOND, ONE_SECOND_NANOSECOND,
     ONE_WEEK_NANOSECOND, ONE_YEAR_NANOSECOND,
 };
+use rust_decimal::Decimal;
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
 use winnow::ascii::multispace0;
-use winnow::combinator::{cut_err, eof, peek};
+use winnow::combinator::{cut_err, eof, fail, peek};
 use winnow::error::{ContextError, StrContext, StrContextValue};
 use winnow::stream::AsChar;
 use winnow::token::{one_of, take_while};
 use winnow::ModalResult as WResult;
 use winnow::Parser;
 
-#[derive(Debug, Eq, PartialEq, Default, Clone)]
-pub(crate) enum TimeUnit {
+/// A unit of time, e.g. the unit given to bare numbers.
+///
+/// A year is 365 days and a month is 30 days, unless configured otherwise with
+/// [`DurationParser::year_length`] and [`DurationParser::month_length`].
+#[derive(Debug, Eq, PartialEq, Default, Clone, Copy, Hash)]
+pub enum TimeUnit {
     Year,
     Month,
     Week,
@@ -48,7 +53,6 @@ impl Display for TimeUnit {
 
 impl TimeUnit {
     #[inline]
-    #[cfg(feature = "cn_unit")]
     fn is_cn_unit(c: char) -> bool {
         [
             '年', '月', '周', '日', '天', '时', '分', '秒', '毫', '微', '纳',
@@ -56,12 +60,79 @@ impl TimeUnit {
         .contains(&c)
     }
 
-    pub(crate) fn duration(&self, time_str: impl AsRef<str>) -> DResult<u64> {
-        let time = time_str
-            .as_ref()
-            .parse::<u64>()
-            .map_err(|err| DError::ParseError(err.to_string()))?;
-        let unit = match self {
+    /// Look up a unit by name.
+    pub(crate) fn from_alias(name: &str, case_sensitive: bool, cn_unit: bool) -> Option<TimeUnit> {
+        let cn_aliases: &[_] = if cn_unit { &CN_UNIT_ALIASES } else { &[] };
+        UNIT_ALIASES
+            .iter()
+            .chain(cn_aliases)
+            .find(|(alias, _)| {
+                if case_sensitive {
+                    *alias == name
+                } else {
+                    alias.eq_ignore_ascii_case(name)
+                }
+            })
+            .map(|(_, unit)| *unit)
+    }
+
+    /// The alias closest to a misspelled unit name such as `minuts` or `hrs`, if any is close
+    /// enough. Only aliases `parser` accepts are suggested.
+    pub(crate) fn suggest(name: &str, parser: &DurationParser) -> Option<&'static str> {
+        // Aliases are lowercase; a wrongly capitalised name is a typo like any other.
+        let name: Vec<char> = name.chars().map(|c| c.to_ascii_lowercase()).collect();
+        if name.is_empty() {
+            return None;
+        }
+        let cn_aliases: &[_] = if parser.cn_unit {
+            &CN_UNIT_ALIASES
+        } else {
+            &[]
+        };
+        // Scores are doubled edit distances, plus one when only a prefix of the alias matched,
+        // so `mili` suggests `millisecond` rather than `min`.
+        let max_score = 2 * (name.len() / 3).max(1) + 1;
+        UNIT_ALIASES
+            .iter()
+            .chain(cn_aliases)
+            .filter(|(_, unit)| parser.allows(*unit))
+            .filter_map(|(alias, _)| {
+                let alias_chars: Vec<char> = alias.chars().collect();
+                let distance = edit_distance(&name, &alias_chars);
+                // A suggestion has to keep something of the input: `x` is not a typo of `y`.
+                let mut score =
+                    (distance < name.len().max(alias_chars.len())).then_some(2 * distance);
+                if name.len() >= 3 && alias_chars.len() > name.len() {
+                    let prefix = 2 * edit_distance(&name, &alias_chars[..name.len()]) + 1;
+                    score = Some(score.map_or(prefix, |score| score.min(prefix)));
+                }
+                score
+                    .filter(|score| *score <= max_score)
+                    .map(|score| (score, *alias))
+            })
+            .min_by_key(|(score, _)| *score)
+            .map(|(_, alias)| alias)
+    }
+
+    /// Convert a quantity of this unit to an exact amount of nanoseconds.
+    ///
+    /// The quantity may carry a fractional part (e.g. `1.5`), so the result is not rounded;
+    /// see [`round_nanos`](crate::round_nanos).
+    pub(crate) fn nanos(&self, time_str: impl AsRef<str>) -> DResult<Decimal> {
+        quantity_nanos(time_str, self.nanos_per_unit().into())
+    }
+
+    /// Whether the length of this unit depends on the calendar: years, months, weeks and days.
+    pub(crate) fn is_calendar(&self) -> bool {
+        matches!(
+            self,
+            TimeUnit::Year | TimeUnit::Month | TimeUnit::Week | TimeUnit::Day
+        )
+    }
+
+    /// Length of one unit in nanoseconds.
+    pub(crate) fn nanos_per_unit(&self) -> u64 {
+        match self {
             TimeUnit::Year => ONE_YEAR_NANOSECOND,
             TimeUnit::Month => ONE_MONTH_NANOSECOND,
             TimeUnit::Week => ONE_WEEK_NANOSECOND,
@@ -72,59 +143,114 @@ impl TimeUnit {
             TimeUnit::MilliSecond => ONE_MILLISECOND_NANOSECOND,
             TimeUnit::MicroSecond => ONE_MICROSECOND_NANOSECOND,
             TimeUnit::NanoSecond => 1,
-        };
-        time.checked_mul(unit).ok_or(DError::OverflowError)
+        }
+    }
+}
+
+/// Convert a quantity of a unit that is `unit_nanos` long to an exact amount of nanoseconds.
+pub(crate) fn quantity_nanos(time_str: impl AsRef<str>, unit_nanos: Decimal) -> DResult<Decimal> {
+    let time = Decimal::from_str(time_str.as_ref()).map_err(|err| match err {
+        rust_decimal::Error::ExceedsMaximumPossibleValue
+        | rust_decimal::Error::LessThanMinimumPossibleValue => DError::OverflowError,
+        // a number with more digits than a decimal holds
+        rust_decimal::Error::ErrorString(msg) if msg.contains("overflow") => DError::OverflowError,
+        err => DError::ParseError(err.to_string()),
+    })?;
+    time.checked_mul(unit_nanos).ok_or(DError::OverflowError)
+}
+
+/// Damerau–Levenshtein distance (with adjacent transpositions) between two names.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
     }
+    rows[a.len()][b.len()]
 }
 
+/// Unit names in lowercase; without case sensitivity they match in any case.
+pub(crate) const UNIT_ALIASES: [(&str, TimeUnit); 40] = [
+    ("y", TimeUnit::Year),
+    ("year", TimeUnit::Year),
+    ("years", TimeUnit::Year),
+    ("mon", TimeUnit::Month),
+    ("month", TimeUnit::Month),
+    ("months", TimeUnit::Month),
+    ("w", TimeUnit::Week),
+    ("week", TimeUnit::Week),
+    ("weeks", TimeUnit::Week),
+    ("d", TimeUnit::Day),
+    ("day", TimeUnit::Day),
+    ("days", TimeUnit::Day),
+    ("h", TimeUnit::Hour),
+    ("hr", TimeUnit::Hour),
+    ("hour", TimeUnit::Hour),
+    ("hours", TimeUnit::Hour),
+    ("m", TimeUnit::Minute),
+    ("min", TimeUnit::Minute),
+    ("minute", TimeUnit::Minute),
+    ("minutes", TimeUnit::Minute),
+    ("s", TimeUnit::Second),
+    ("sec", TimeUnit::Second),
+    ("second", TimeUnit::Second),
+    ("seconds", TimeUnit::Second),
+    ("ms", TimeUnit::MilliSecond),
+    ("msec", TimeUnit::MilliSecond),
+    ("millisecond", TimeUnit::MilliSecond),
+    ("milliseconds", TimeUnit::MilliSecond),
+    ("µs", TimeUnit::MicroSecond),
+    ("µsec", TimeUnit::MicroSecond),
+    ("µsecond", TimeUnit::MicroSecond),
+    ("us", TimeUnit::MicroSecond),
+    ("usec", TimeUnit::MicroSecond),
+    ("usecond", TimeUnit::MicroSecond),
+    ("microsecond", TimeUnit::MicroSecond),
+    ("microseconds", TimeUnit::MicroSecond),
+    ("ns", TimeUnit::NanoSecond),
+    ("nsec", TimeUnit::NanoSecond),
+    ("nanosecond", TimeUnit::NanoSecond),
+    ("nanoseconds", TimeUnit::NanoSecond),
+];
+
+/// Chinese unit names, accepted when `cn_unit` is enabled.
+pub(crate) const CN_UNIT_ALIASES: [(&str, TimeUnit); 11] = [
+    ("年", TimeUnit::Year),
+    ("月", TimeUnit::Month),
+    ("周", TimeUnit::Week),
+    ("日", TimeUnit::Day),
+    ("天", TimeUnit::Day),
+    ("时", TimeUnit::Hour),
+    ("分", TimeUnit::Minute),
+    ("秒", TimeUnit::Second),
+    ("毫秒", TimeUnit::MilliSecond),
+    ("微秒", TimeUnit::MicroSecond),
+    ("纳秒", TimeUnit::NanoSecond),
+];
+
 impl FromStr for TimeUnit {
     type Err = DError;
 
+    /// Look up a unit name the way the default [`DurationParser`] does.
     #[inline(always)]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let owned;
-        let case = if cfg!(feature = "lowercase") {
-            s
-        } else {
-            owned = s.to_ascii_lowercase();
-            owned.as_str()
-        };
-
-        match case {
-            "y" | "year" | "years" => Ok(TimeUnit::Year),
-            "mon" | "month" | "months" => Ok(TimeUnit::Month),
-            "w" | "week" | "weeks" => Ok(TimeUnit::Week),
-            "d" | "day" | "days" => Ok(TimeUnit::Day),
-            "h" | "hr" | "hour" | "hours" => Ok(TimeUnit::Hour),
-            "m" | "min" | "minute" | "minutes" => Ok(TimeUnit::Minute),
-            "s" | "sec" | "second" | "seconds" => Ok(TimeUnit::Second),
-            "ms" | "msec" | "millisecond" | "milliseconds" => Ok(TimeUnit::MilliSecond),
-            "µs" | "µsec" | "µsecond" | "us" | "usec" | "usecond" | "microsecond"
-            | "microseconds" => Ok(TimeUnit::MicroSecond),
-            "ns" | "nsec" | "nanosecond" | "nanoseconds" => Ok(TimeUnit::NanoSecond),
-
-            #[cfg(feature = "cn_unit")]
-            "年" => Ok(TimeUnit::Year),
-            #[cfg(feature = "cn_unit")]
-            "月" => Ok(TimeUnit::Month),
-            #[cfg(feature = "cn_unit")]
-            "周" => Ok(TimeUnit::Week),
-            #[cfg(feature = "cn_unit")]
-            "日" | "天" => Ok(TimeUnit::Day),
-            #[cfg(feature = "cn_unit")]
-            "时" => Ok(TimeUnit::Hour),
-            #[cfg(feature = "cn_unit")]
-            "分" => Ok(TimeUnit::Minute),
-            #[cfg(feature = "cn_unit")]
-            "秒" => Ok(TimeUnit::Second),
-            #[cfg(feature = "cn_unit")]
-            "毫秒" => Ok(TimeUnit::MilliSecond),
-            #[cfg(feature = "cn_unit")]
-            "微秒" => Ok(TimeUnit::MicroSecond),
-            #[cfg(feature = "cn_unit")]
-            "纳秒" => Ok(TimeUnit::NanoSecond),
-            _ => Err(DError::ParseError(Self::expect_err(case))),
-        }
+        let parser = DurationParser::new();
+        TimeUnit::from_alias(s, parser.case_sensitive, parser.cn_unit).ok_or_else(|| {
+            let mut err = Self::expect_err(s);
+            if let Some(alias) = TimeUnit::suggest(s, &parser) {
+                err.push_str(&format!(", did you mean `{}`?", alias));
+            }
+            DError::ParseError(err)
+        })
     }
 }
 
@@ -134,39 +260,42 @@ impl_expect_err!(
     ["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"]
 );
 
-pub(crate) fn unit_abbr1(input: &mut &str) -> WResult<TimeUnit> {
-    let set = |c: char| c.is_alpha() || c == 'µ';
-    let set = {
-        #[cfg(feature = "cn_unit")]
-        {
-            move |c: char| set(c) || TimeUnit::is_cn_unit(c)
-        }
-        #[cfg(not(feature = "cn_unit"))]
-        set
-    };
+pub(crate) fn unit_abbr1(input: &mut &str, parser: &DurationParser) -> WResult<TimeUnit> {
+    let set = |c: char| c.is_alpha() || c == 'µ' || (parser.cn_unit && TimeUnit::is_cn_unit(c));
 
     take_while(1.., set)
-        .parse_to()
+        .verify_map(|name| {
+            TimeUnit::from_alias(name, parser.case_sensitive, parser.cn_unit)
+                .filter(|unit| parser.allows(*unit))
+        })
         .context(StrContext::Expected(StrContextValue::Description(
             TimeUnit::get_expect_val(),
         )))
         .parse_next(input)
 }
 
-pub(crate) fn opt_unit_abbr(input: &mut &str) -> WResult<TimeUnit> {
-    let result = unit_abbr1(input);
+pub(crate) fn opt_unit_abbr(input: &mut &str, parser: &DurationParser) -> WResult<TimeUnit> {
+    let result = unit_abbr1(input, parser);
     if result.is_err() {
         multispace0.parse_next(input)?;
+        if parser.strict {
+            // Bare numbers need a unit in strict mode.
+            return cut_err(fail)
+                .context(StrContext::Expected(StrContextValue::Description(
+                    TimeUnit::get_expect_val(),
+                )))
+                .parse_next(input);
+        }
         if eof::<_, ContextError>.parse_next(input).is_ok() {
-            // The input result is empty except for spaces. Give `TimeUnit` default value
-            return Ok(TimeUnit::default());
+            // The input result is empty except for spaces. Use the parser's default unit
+            return Ok(parser.default_unit);
         }
 
-        return cut_err(peek(one_of(CondUnit::contain)))
+        return cut_err(peek(one_of(|c| CondUnit::contain(c) || c == ')')))
             .context(StrContext::Expected(StrContextValue::Description(
                 TimeUnit::get_expect_val(),
             )))
-            .value(TimeUnit::default())
+            .value(parser.default_unit)
             .parse_next(input);
     }
     result
@@ -178,6 +307,15 @@ mod tests {
     use crate::catch_err;
     use winnow::{Parser, Partial};
 
+    // shorthands with the default parser configuration
+    fn unit_abbr1(input: &mut &str) -> WResult<TimeUnit> {
+        super::unit_abbr1(input, &DurationParser::new())
+    }
+
+    fn opt_unit_abbr(input: &mut &str) -> WResult<TimeUnit> {
+        super::opt_unit_abbr(input, &DurationParser::new())
+    }
+
     #[test]
     fn test_time_unit_abbr() {
         assert_eq!(
@@ -278,6 +416,62 @@ mod tests {
         assert_eq!(format, TimeUnit::Minute);
     }
 
+    #[test]
+    fn test_time_unit_from_str_suggestion() {
+        assert_eq!(
+            TimeUnit::from_str("minuts"),
+            Err(DError::ParseError(
+                r#"expect one of:["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"], but find:minuts, did you mean `minute`?"#
+                    .to_string()
+            ))
+        );
+        assert_eq!(
+            TimeUnit::from_str("x"),
+            Err(DError::ParseError(
+                r#"expect one of:["y", "mon", "w", "d", "h", "m", "s", "ms", "µs", "us", "ns"], but find:x"#
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let distance = |a: &str, b: &str| {
+            edit_distance(
+                &a.chars().collect::<Vec<_>>(),
+                &b.chars().collect::<Vec<_>>(),
+            )
+        };
+        assert_eq!(distance("minuts", "minutes"), 1);
+        assert_eq!(distance("dya", "day"), 1);
+        assert_eq!(distance("hrs", "hours"), 2);
+        assert_eq!(distance("", "ms"), 2);
+        assert_eq!(distance("毫", "毫秒"), 1);
+    }
+
+    #[test]
+    fn test_time_unit_nanos() {
+        let nanos = |n: u64| Ok(Decimal::from(n));
+        assert_eq!(TimeUnit::Hour.nanos("2"), nanos(2 * ONE_HOUR_NANOSECOND));
+        assert_eq!(
+            TimeUnit::Hour.nanos("1.5"),
+            nanos(90 * ONE_MINUTE_NANOSECOND)
+        );
+        assert_eq!(TimeUnit::Day.nanos("0.25"), nanos(6 * ONE_HOUR_NANOSECOND));
+        assert_eq!(
+            TimeUnit::Minute.nanos("-1.5"),
+            Ok(-Decimal::from(90 * ONE_SECOND_NANOSECOND))
+        );
+        assert_eq!(
+            TimeUnit::MicroSecond.nanos("0.0015"),
+            Ok(Decimal::new(15, 1))
+        );
+        assert_eq!(
+            TimeUnit::Year.nanos("100000000000000000000"),
+            Err(DError::OverflowError)
+        );
+    }
+
     #[test]
     fn test_unit_abbr1_err() {
         let expect_err = r#"